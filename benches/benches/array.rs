@@ -1,5 +1,8 @@
 use implicit_clone::unsync::*;
 
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
+
 fn main() {
     divan::main();
 }
@@ -24,3 +27,42 @@ fn from_iter_collection_with_multiple_elements(bencher: divan::Bencher) {
         let _: IArray<u32> = divan::black_box(vec![42, 43]).into_iter().collect();
     });
 }
+
+#[divan::bench(args = [2, 16, 256])]
+fn from_iter_exact_size_iterator(bencher: divan::Bencher, len: u32) {
+    let vec = (0..len).collect::<Vec<u32>>();
+    bencher.bench_local(|| {
+        let _: IArray<u32> = divan::black_box(vec.iter().copied().map(|x| x + 1)).collect();
+    });
+}
+
+#[divan::bench(args = [2, 16, 256])]
+fn from_iter_exact(bencher: divan::Bencher, len: u32) {
+    let vec = (0..len).collect::<Vec<u32>>();
+    bencher.bench_local(|| {
+        let _: IArray<u32> =
+            IArray::from_iter_exact(divan::black_box(vec.iter().copied().map(|x| x + 1)));
+    });
+}
+
+#[divan::bench(args = [2, 16, 256])]
+fn from_slice_owned(bencher: divan::Bencher, len: u32) {
+    let vec = (0..len).collect::<Vec<u32>>();
+    bencher.bench_local(|| {
+        let _: IArray<u32> = IArray::from_slice_owned(divan::black_box(&vec));
+    });
+}
+
+#[divan::bench(args = [1, 2, 256, 100_000])]
+fn from_vec(bencher: divan::Bencher, len: u32) {
+    bencher
+        .with_inputs(|| (0..len).collect::<Vec<u32>>())
+        .bench_local_values(|vec| -> IArray<u32> { IArray::from(vec) });
+}
+
+#[divan::bench(args = [1, 2, 256, 100_000])]
+fn from_vec_exact(bencher: divan::Bencher, len: u32) {
+    bencher
+        .with_inputs(|| (0..len).collect::<Vec<u32>>())
+        .bench_local_values(IArray::<u32>::from_vec_exact);
+}
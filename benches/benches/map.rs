@@ -0,0 +1,44 @@
+use implicit_clone::unsync::*;
+
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
+
+fn main() {
+    divan::main();
+}
+
+fn entries(len: u32) -> Vec<(u32, u32)> {
+    (0..len).map(|i| (i, i * 2)).collect()
+}
+
+#[divan::bench(args = [2, 16, 256])]
+fn from_iter(bencher: divan::Bencher, len: u32) {
+    let entries = entries(len);
+    bencher.bench_local(|| {
+        let _: IMap<u32, u32> = divan::black_box(entries.iter().copied()).collect();
+    });
+}
+
+#[divan::bench(args = [2, 16, 256])]
+fn get(bencher: divan::Bencher, len: u32) {
+    let map = entries(len).into_iter().collect::<IMap<u32, u32>>();
+    bencher.bench_local(|| divan::black_box(&map).get(&divan::black_box(len / 2)));
+}
+
+#[divan::bench(args = [2, 16, 256])]
+fn get_static(bencher: divan::Bencher, len: u32) {
+    let map = IMap::<u32, u32>::Static(Box::leak(entries(len).into_boxed_slice()));
+    bencher.bench_local(|| divan::black_box(&map).get(&divan::black_box(len / 2)));
+}
+
+#[divan::bench(args = [2, 16, 256])]
+fn try_insert(bencher: divan::Bencher, len: u32) {
+    let map = entries(len).into_iter().collect::<IMap<u32, u32>>();
+    bencher.bench_local(|| divan::black_box(&map).try_insert(len, 0));
+}
+
+#[divan::bench(sample_size = 10000000)]
+fn clone(bencher: divan::Bencher) {
+    let map = entries(16).into_iter().collect::<IMap<u32, u32>>();
+    bencher.bench_local(|| divan::black_box(&map).clone());
+}
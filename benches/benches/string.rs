@@ -0,0 +1,33 @@
+use implicit_clone::unsync::*;
+
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
+
+fn main() {
+    divan::main();
+}
+
+#[divan::bench(args = [0, 16, 256])]
+fn from_string(bencher: divan::Bencher, len: usize) {
+    bencher
+        .with_inputs(|| "a".repeat(len))
+        .bench_local_values(IString::from);
+}
+
+#[divan::bench(args = [0, 16, 256])]
+fn from_str_owned(bencher: divan::Bencher, len: usize) {
+    let s = "a".repeat(len);
+    bencher.bench_local(|| IString::from_str_owned(divan::black_box(&s)));
+}
+
+#[divan::bench(sample_size = 10000000)]
+fn clone_static(bencher: divan::Bencher) {
+    let s = IString::Static("foo");
+    bencher.bench_local(|| divan::black_box(&s).clone());
+}
+
+#[divan::bench(sample_size = 10000000)]
+fn clone_rc(bencher: divan::Bencher) {
+    let s = IString::from(String::from("foo"));
+    bencher.bench_local(|| divan::black_box(&s).clone());
+}
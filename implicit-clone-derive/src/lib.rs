@@ -15,8 +15,9 @@ pub fn derive_implicit_clone(item: proc_macro::TokenStream) -> proc_macro::Token
                 ident,
                 colon_token: _,
                 bounds,
-                eq_token,
-                default,
+                // defaults are not allowed in the generics of an impl
+                eq_token: _,
+                default: _,
             }) => {
                 let bounds = bounds
                     .iter()
@@ -24,9 +25,20 @@ pub fn derive_implicit_clone(item: proc_macro::TokenStream) -> proc_macro::Token
                     .chain(std::iter::once(quote! { ::implicit_clone::ImplicitClone }))
                     .collect::<Vec<_>>();
                 quote! {
-                    #(#attrs)* #ident: #(#bounds)+* #eq_token #default
+                    #(#attrs)* #ident: #(#bounds)+*
                 }
             }
+            syn::GenericParam::Const(syn::ConstParam {
+                attrs,
+                const_token,
+                ident,
+                colon_token,
+                ty,
+                eq_token: _,
+                default: _,
+            }) => quote! {
+                #(#attrs)* #const_token #ident #colon_token #ty
+            },
             _ => quote! { #param },
         })
         .collect::<Vec<_>>();
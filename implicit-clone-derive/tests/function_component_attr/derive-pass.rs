@@ -17,6 +17,14 @@ enum EnumWithGenerics<T> {
     Variant(T),
 }
 
+#[derive(Clone, ImplicitClone)]
+struct StructWithDefaultGenerics<T = u32>(std::rc::Rc<T>);
+
+#[derive(Clone, ImplicitClone)]
+struct StructWithDefaultGenericsWithBounds<T: PartialEq = u32, const N: usize = 2>([T; N]);
+
 fn main() {
+    let _ = ImplicitClone::implicit_clone(&StructWithDefaultGenerics(std::rc::Rc::new(1)));
+    let _ = ImplicitClone::implicit_clone(&StructWithDefaultGenericsWithBounds([1, 2]));
     let _ = ImplicitClone::implicit_clone(&ExampleStruct);
 }
@@ -7,7 +7,12 @@ use crate::ImplicitClone;
 ///
 /// This type is cheap to clone and thus implements [`ImplicitClone`]. It can be created based on a
 /// `&'static [T]` or based on a reference counted slice (`T`).
-#[derive(PartialEq, Eq)]
+///
+/// An `IArray<T>` is three words large (the size of a `&[T]` plus a tag), or the size of the
+/// `Single` variant if `T` is larger than two words. This is checked by the tests of this crate.
+/// There is no feature to remove the `Single` variant for large `T`: features must be additive,
+/// and removing a variant would break every crate in the dependency graph that matches on it.
+#[derive(Eq)]
 pub enum IArray<T: ImplicitClone + 'static> {
     /// A static slice.
     Static(&'static [T]),
@@ -17,14 +22,9 @@ pub enum IArray<T: ImplicitClone + 'static> {
     Single([T; 1]),
 }
 
-// TODO add insta tests
 impl<T: fmt::Debug + ImplicitClone + 'static> fmt::Debug for IArray<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Self::Static(a) => a.fmt(f),
-            Self::Rc(a) => a.fmt(f),
-            Self::Single(x) => x.fmt(f),
-        }
+        f.debug_list().entries(self.as_slice()).finish()
     }
 }
 
@@ -69,6 +69,9 @@ impl<T: ImplicitClone + 'static> From<&'static [T]> for IArray<T> {
     }
 }
 
+/// Moves the elements into a new `Rc<[T]>`, which requires one new allocation: the vector's
+/// allocation cannot be reused because the reference counts are stored in front of the elements.
+/// See [`IArray::from_vec_exact`] to avoid allocating for empty and single-element vectors.
 impl<T: ImplicitClone + 'static> From<Vec<T>> for IArray<T> {
     fn from(a: Vec<T>) -> IArray<T> {
         IArray::Rc(Rc::from(a))
@@ -93,6 +96,22 @@ impl<T: ImplicitClone + 'static> From<[T; 1]> for IArray<T> {
     }
 }
 
+/// An edit in the script returned by [`IArray::diff`].
+///
+/// Applying the edits in order turns the old array into the new one: `Keep` and `Insert` append an
+/// element to the result, while `Remove` skips an element of the old array.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArrayEdit<T> {
+    /// Keep the element at this index of the old array.
+    Keep(usize),
+    /// Insert the element, which is at this index of the new array.
+    Insert(usize, T),
+    /// Remove the element at this index of the old array.
+    Remove(usize),
+}
+
+impl<T: ImplicitClone> ImplicitClone for ArrayEdit<T> {}
+
 /// An iterator over the elements of an `IArray`.
 #[derive(Debug)]
 pub struct Iter<T: ImplicitClone + 'static> {
@@ -120,6 +139,179 @@ impl<T: ImplicitClone + 'static> IArray<T> {
     /// An empty array without allocation.
     pub const EMPTY: Self = Self::Static(&[]);
 
+    /// Creates an array from a static slice without allocation.
+    ///
+    /// This is usable in `const` contexts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// const ARRAY: IArray<u32> = IArray::from_static(&[1, 2, 3]);
+    /// assert_eq!(ARRAY, [1, 2, 3]);
+    /// ```
+    #[inline]
+    pub const fn from_static(slice: &'static [T]) -> Self {
+        Self::Static(slice)
+    }
+
+    /// Creates an array by cloning the elements of a slice that is not `'static`.
+    ///
+    /// A borrowing variant is not possible as `IArray` does not carry a lifetime, so the elements
+    /// are cloned into a new reference counted slice (or a `Single` element).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let vec = vec![1, 2, 3];
+    /// let array = IArray::<u8>::from_slice_owned(&vec);
+    /// assert_eq!(array, [1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn from_slice_owned(slice: &[T]) -> Self {
+        Self::from_iter_exact(slice.iter().cloned())
+    }
+
+    /// Creates an array from an iterator whose length is known in advance.
+    ///
+    /// Unlike the [`FromIterator`] implementation, which relies on the iterator's size hint, the
+    /// exact length is always used to pick the most compact variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u8>::from_iter_exact([1, 2, 3].into_iter());
+    /// assert_eq!(array, [1, 2, 3]);
+    /// ```
+    pub fn from_iter_exact<I: ExactSizeIterator<Item = T>>(mut it: I) -> Self {
+        match it.len() {
+            0 => Self::EMPTY,
+            1 => match it.next() {
+                Some(element) => Self::Single([element]),
+                None => Self::EMPTY,
+            },
+            _ => Self::Rc(Rc::from_iter(it)),
+        }
+    }
+
+    /// Creates an array from an owned vector, picking the most compact variant.
+    ///
+    /// The vector's allocation cannot be reused: a reference counted slice stores its counters
+    /// next to the elements, so the elements are always moved into a new allocation. An empty
+    /// vector or a vector with a single element does not allocate at all (unlike
+    /// `IArray::from(vec)`, which always creates an `Rc`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u8>::from_vec_exact(vec![1, 2, 3]);
+    /// assert_eq!(array, [1, 2, 3]);
+    /// assert!(matches!(IArray::<u8>::from_vec_exact(vec![1]), IArray::Single([1])));
+    /// ```
+    pub fn from_vec_exact(mut v: Vec<T>) -> Self {
+        match v.len() {
+            0 => Self::EMPTY,
+            1 => match v.pop() {
+                Some(element) => Self::Single([element]),
+                None => Self::EMPTY,
+            },
+            _ => Self::Rc(Rc::from(v)),
+        }
+    }
+
+    /// Concatenates all the arrays of a slice into one.
+    ///
+    /// Empty arrays are skipped, and if only one array is not empty it is returned as-is without
+    /// copying its elements. Otherwise, the elements are cloned into a new reference counted slice.
+    ///
+    /// `IArray<T>` deliberately does not implement `FromIterator<IArray<T>>`. With a second
+    /// `FromIterator` impl, collecting an iterator whose item type is not otherwise known, such as
+    /// `[].into_iter().collect::<IArray<u32>>()`, would no longer compile, so use this function to
+    /// flatten arrays instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let arrays = [IArray::<u8>::Static(&[1, 2]), IArray::EMPTY, IArray::from(vec![3])];
+    /// assert_eq!(IArray::concat_all(&arrays), [1, 2, 3]);
+    /// ```
+    pub fn concat_all(arrays: &[IArray<T>]) -> Self {
+        let mut non_empty = arrays.iter().filter(|array| !array.is_empty());
+        match (non_empty.next(), non_empty.next()) {
+            (None, _) => Self::EMPTY,
+            (Some(array), None) => array.clone(),
+            _ => {
+                let len = arrays.iter().map(|array| array.len()).sum();
+                let mut vec = Vec::with_capacity(len);
+                for array in arrays {
+                    vec.extend_from_slice(array.as_slice());
+                }
+                Self::from(vec)
+            }
+        }
+    }
+
+    /// Creates an array from a reference counted vector.
+    ///
+    /// The elements are moved into a new `Rc<[T]>`, which requires one new allocation. If the
+    /// `Rc<Vec<T>>` is shared, the elements are cloned instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// # use std::rc::Rc;
+    /// let array = IArray::<u8>::from_rc_vec(Rc::new(vec![1, 2, 3]));
+    /// assert_eq!(array, [1, 2, 3]);
+    /// ```
+    pub fn from_rc_vec(a: Rc<Vec<T>>) -> Self {
+        let vec = Rc::try_unwrap(a).unwrap_or_else(|a| a.as_ref().clone());
+        Self::Rc(Rc::from(vec))
+    }
+
+    /// Creates an array of `N` elements where each element is produced by calling `f` with its
+    /// index.
+    ///
+    /// This mirrors [`std::array::from_fn`]. See [`generate`](Self::generate) for a length only
+    /// known at runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<usize>::from_fn::<4>(|i| i * i);
+    /// assert_eq!(array, [0, 1, 4, 9]);
+    /// ```
+    #[inline]
+    pub fn from_fn<const N: usize>(f: impl FnMut(usize) -> T) -> Self {
+        Self::generate(N, f)
+    }
+
+    /// Creates an array of `n` elements where each element is produced by calling `f` with its
+    /// index.
+    ///
+    /// The elements are written in a single allocation. An array of zero or one element does not
+    /// allocate at all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<IString>::generate(3, |i| IString::from(i.to_string()));
+    /// assert_eq!(array, ["0", "1", "2"]);
+    /// ```
+    pub fn generate(n: usize, mut f: impl FnMut(usize) -> T) -> Self {
+        match n {
+            0 => Self::EMPTY,
+            1 => Self::Single([f(0)]),
+            _ => Self::Rc((0..n).map(f).collect()),
+        }
+    }
+
     /// Returns an iterator over the slice.
     ///
     /// # Examples
@@ -179,6 +371,29 @@ impl<T: ImplicitClone + 'static> IArray<T> {
         }
     }
 
+    /// Returns the number of elements held by the backing allocation.
+    ///
+    /// A reference counted slice never has spare capacity so this is its length. This is `1` for
+    /// a `Single` element, which is stored inline, and `0` for a `Static` slice, which is not
+    /// owned by the array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// assert_eq!(IArray::<u8>::Static(&[1, 2, 3]).capacity(), 0);
+    /// assert_eq!(IArray::<u8>::Single([1]).capacity(), 1);
+    /// assert_eq!(IArray::<u8>::from(vec![1, 2, 3]).capacity(), 3);
+    /// ```
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        match self {
+            Self::Static(_) => 0,
+            Self::Rc(a) => a.len(),
+            Self::Single(_) => 1,
+        }
+    }
+
     /// Extracts a slice containing the entire array.
     ///
     /// Equivalent to `&s[..]`.
@@ -200,158 +415,1048 @@ impl<T: ImplicitClone + 'static> IArray<T> {
         }
     }
 
-    /// Returns a clone of an element at a position or `None` if out of bounds.
+    /// Returns a raw pointer to the array's buffer.
+    ///
+    /// The pointer is valid for [`len`](Self::len) elements for as long as `self` is borrowed.
+    /// Note that a `Single` element is stored inline, so moving the array invalidates the pointer.
     ///
     /// # Examples
     ///
     /// ```
     /// # use implicit_clone::unsync::*;
-    /// let v = IArray::<u8>::Static(&[10, 40, 30]);
-    /// assert_eq!(Some(40), v.get(1));
-    /// assert_eq!(None, v.get(3));
+    /// let array = IArray::<u8>::Static(&[1, 2, 3]);
+    /// let ptr = array.as_ptr();
+    /// assert_eq!(unsafe { *ptr.add(1) }, 2);
     /// ```
     #[inline]
-    pub fn get(&self, index: usize) -> Option<T> {
+    pub fn as_ptr(&self) -> *const T {
+        self.as_slice().as_ptr()
+    }
+
+    /// Returns an array with the first `n` elements, or the whole array if it has fewer than `n`
+    /// elements.
+    ///
+    /// A `Static` array returns a `Static` sub-slice and the whole array is returned without
+    /// cloning its elements when `n` covers it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u8>::from(vec![1, 2, 3]);
+    /// assert_eq!(array.first_n(2), [1, 2]);
+    /// assert_eq!(array.first_n(5), [1, 2, 3]);
+    /// ```
+    pub fn first_n(&self, n: usize) -> IArray<T> {
+        if n >= self.len() {
+            return self.clone();
+        }
         match self {
-            Self::Static(a) => a.get(index).cloned(),
-            Self::Rc(a) => a.get(index).cloned(),
-            Self::Single(a) if index == 0 => Some(a[0].clone()),
-            Self::Single(_) => None,
+            Self::Static(a) => Self::Static(&a[..n]),
+            _ => self.as_slice()[..n].iter().cloned().collect(),
         }
     }
 
-    /// Gets a mutable reference into the array, if there are no other references.
+    /// Returns an array with the last `n` elements, or the whole array if it has fewer than `n`
+    /// elements.
     ///
-    /// If this array is an `Rc` with no other strong or weak references, returns
-    /// a mutable slice of the contained data without any cloning. Otherwise returns
-    /// `None`.
+    /// A `Static` array returns a `Static` sub-slice and the whole array is returned without
+    /// cloning its elements when `n` covers it.
     ///
-    /// # Example
+    /// # Examples
     ///
     /// ```
     /// # use implicit_clone::unsync::*;
-    /// # use std::rc::Rc;
-    /// // This will reuse the Rc storage
-    /// let mut v1 = IArray::<u8>::Rc(Rc::new([1,2,3]));
-    /// v1.get_mut().unwrap()[1] = 123;
-    /// assert_eq!(&[1,123,3], v1.as_slice());
+    /// let array = IArray::<u8>::from(vec![1, 2, 3]);
+    /// assert_eq!(array.last_n(2), [2, 3]);
+    /// assert_eq!(array.last_n(5), [1, 2, 3]);
+    /// ```
+    pub fn last_n(&self, n: usize) -> IArray<T> {
+        let len = self.len();
+        if n >= len {
+            return self.clone();
+        }
+        match self {
+            Self::Static(a) => Self::Static(&a[len - n..]),
+            _ => self.as_slice()[len - n..].iter().cloned().collect(),
+        }
+    }
+
+    /// Returns a new array with every `step`-th element, starting with the first one.
     ///
-    /// // Another reference will prevent exclusive access
-    /// let v2 = v1.clone();
-    /// assert!(v1.get_mut().is_none());
+    /// # Panics
     ///
-    /// // Static references are immutable
-    /// let mut v3 = IArray::<u8>::Static(&[1,2,3]);
-    /// assert!(v3.get_mut().is_none());
+    /// Panics if `step` is 0.
+    ///
+    /// # Examples
     ///
-    /// // Single items always return a mutable reference
-    /// let mut v4 = IArray::<u8>::Single([1]);
-    /// assert!(v4.get_mut().is_some());
     /// ```
-    #[inline]
-    pub fn get_mut(&mut self) -> Option<&mut [T]> {
-        match self {
-            Self::Rc(ref mut rc) => Rc::get_mut(rc),
-            Self::Static(_) => None,
-            Self::Single(ref mut a) => Some(a),
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u8>::Static(&[0, 1, 2, 3, 4, 5]);
+    /// assert_eq!(array.step_by(2), [0, 2, 4]);
+    /// ```
+    pub fn step_by(&self, step: usize) -> IArray<T> {
+        assert!(step != 0, "step must be non-zero");
+        if step == 1 {
+            return self.clone();
         }
+        self.as_slice().iter().step_by(step).cloned().collect()
     }
 
-    /// Makes a mutable reference into the array.
+    /// Returns a new array with one element per chunk of `size` elements, produced by `f`.
     ///
-    /// If this array is an `Rc` with no other strong or weak references, returns
-    /// a mutable slice of the contained data without any cloning. Otherwise, it clones the
-    /// data into a new array and returns a mutable slice into that.
+    /// The chunks don't overlap and the last one may be shorter than `size`.
     ///
-    /// If this array is a `Static`, it clones its elements into a new `Rc` array and returns a
-    /// mutable slice into that new array.
+    /// # Panics
     ///
-    /// If this array is a `Single` element, the inner array is returned directly.
+    /// Panics if `size` is 0.
     ///
     /// # Examples
     ///
     /// ```
     /// # use implicit_clone::unsync::*;
-    /// // This will reuse the Rc storage
-    /// let mut data = IArray::<u8>::from(vec![1,2,3]);
-    /// data.make_mut()[1] = 123;
-    /// assert_eq!(&[1,123,3], data.as_slice());
-    /// assert!(matches!(data, IArray::<u8>::Rc(_)));
+    /// let array = IArray::<u8>::Static(&[1, 2, 3, 4, 5]);
+    /// assert_eq!(array.chunk_map(2, |chunk| chunk.len()), [2, 2, 1]);
     /// ```
+    pub fn chunk_map<U, F>(&self, size: usize, f: F) -> IArray<U>
+    where
+        U: ImplicitClone + 'static,
+        F: FnMut(&[T]) -> U,
+    {
+        assert!(size != 0, "chunk size must be non-zero");
+        IArray::from_iter_exact(self.as_slice().chunks(size).map(f))
+    }
+
+    /// Groups the elements of the array into an [`IMap`](super::IMap) keyed by the result of
+    /// `key_fn`.
+    ///
+    /// Keys are kept in the order they are first seen and the elements of each group keep their
+    /// relative order.
+    ///
+    /// # Examples
     ///
     /// ```
     /// # use implicit_clone::unsync::*;
-    /// // This will create a new copy
-    /// let mut data = IArray::<u8>::from(vec![1,2,3]);
-    /// let other_data = data.clone();
-    /// data.make_mut()[1] = 123;
-    /// assert_eq!(&[1,123,3], data.as_slice());
-    /// assert_eq!(&[1,2,3], other_data.as_slice());
-    /// assert!(matches!(data, IArray::<u8>::Rc(_)));
-    /// assert!(matches!(other_data, IArray::<u8>::Rc(_)));
+    /// let array = IArray::<u32>::Static(&[1, 2, 3, 4]);
+    /// let groups = array.group_by_key(|x| x % 2 == 0);
+    /// assert_eq!(groups.get(&false).unwrap(), [1, 3]);
+    /// assert_eq!(groups.get(&true).unwrap(), [2, 4]);
     /// ```
+    #[cfg(feature = "map")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "map")))]
+    pub fn group_by_key<K, F>(&self, mut key_fn: F) -> super::IMap<K, IArray<T>>
+    where
+        // `IMap` requires its values, here the groups, to be `PartialEq`.
+        T: PartialEq,
+        K: Eq + std::hash::Hash + ImplicitClone + 'static,
+        F: FnMut(&T) -> K,
+    {
+        let mut groups = indexmap::IndexMap::<K, Vec<T>>::new();
+        for item in self.as_slice() {
+            groups.entry(key_fn(item)).or_default().push(item.clone());
+        }
+        groups
+            .into_iter()
+            .map(|(k, v)| (k, IArray::from(v)))
+            .collect()
+    }
+
+    /// Returns a new array with every element converted with [`From`].
+    ///
+    /// The elements are cloned and converted one by one, no memory is reinterpreted.
+    ///
+    /// # Examples
     ///
     /// ```
     /// # use implicit_clone::unsync::*;
-    /// // This will create a new copy
-    /// let mut data = IArray::<u8>::Static(&[1,2,3]);
-    /// let other_data = data.clone();
-    /// data.make_mut()[1] = 123;
-    /// assert_eq!(&[1,123,3], data.as_slice());
-    /// assert_eq!(&[1,2,3], other_data.as_slice());
-    /// assert!(matches!(data, IArray::<u8>::Rc(_)));
-    /// assert!(matches!(other_data, IArray::<u8>::Static(_)));
+    /// let array = IArray::<u8>::Static(&[1, 2, 3]);
+    /// let array: IArray<u32> = array.cast();
+    /// assert_eq!(array, [1, 2, 3]);
     /// ```
+    pub fn cast<U>(&self) -> IArray<U>
+    where
+        U: From<T> + ImplicitClone + 'static,
+    {
+        IArray::from_iter_exact(self.as_slice().iter().cloned().map(U::from))
+    }
+
+    /// Returns a new array with every element converted with [`TryFrom`], or `None` if any
+    /// conversion fails.
+    ///
+    /// # Examples
     ///
     /// ```
     /// # use implicit_clone::unsync::*;
-    /// // This will use the inner array directly
-    /// let mut data = IArray::<u8>::Single([1]);
-    /// let other_data = data.clone();
-    /// data.make_mut()[0] = 123;
-    /// assert_eq!(&[123], data.as_slice());
-    /// assert_eq!(&[1], other_data.as_slice());
-    /// assert!(matches!(data, IArray::<u8>::Single(_)));
-    /// assert!(matches!(other_data, IArray::<u8>::Single(_)));
+    /// let array = IArray::<u32>::Static(&[1, 2, 3]);
+    /// assert_eq!(array.try_cast::<u8>(), Some(IArray::from(vec![1, 2, 3])));
+    ///
+    /// let array = IArray::<u32>::Static(&[1, 256]);
+    /// assert_eq!(array.try_cast::<u8>(), None);
     /// ```
-    #[inline]
-    pub fn make_mut(&mut self) -> &mut [T] {
-        match self {
-            Self::Rc(ref mut rc) => {
-                // This code is somewhat weirdly written to work around
-                // https://github.com/rust-lang/rust/issues/54663 - we can't just check if this is
-                // an Rc with one reference with get_mut in an if branch and copy otherwise, since
-                // returning the mutable slice extends its lifetime for the rest of the function.
-                if Rc::get_mut(rc).is_none() {
-                    *rc = rc.iter().cloned().collect::<Rc<[T]>>();
-                }
-                Rc::get_mut(rc).unwrap()
-            }
-            Self::Static(slice) => {
-                *self = Self::Rc(slice.iter().cloned().collect());
-                match self {
-                    Self::Rc(rc) => Rc::get_mut(rc).unwrap(),
-                    _ => unreachable!(),
-                }
-            }
-            Self::Single(array) => array,
-        }
+    pub fn try_cast<U>(&self) -> Option<IArray<U>>
+    where
+        U: TryFrom<T> + ImplicitClone + 'static,
+    {
+        let vec = self
+            .as_slice()
+            .iter()
+            .cloned()
+            .map(|x| U::try_from(x).ok())
+            .collect::<Option<Vec<U>>>()?;
+        Some(IArray::from_vec_exact(vec))
     }
-}
 
-impl<'a, T, U, const N: usize> PartialEq<&'a [U; N]> for IArray<T>
-where
-    T: PartialEq<U> + ImplicitClone,
-{
-    fn eq(&self, other: &&[U; N]) -> bool {
+    /// Returns a clone of the backing `Rc` if this array is an `Rc`, or `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u8>::from(vec![1, 2, 3]);
+    /// assert_eq!(array.as_rc_slice().as_deref(), Some(&[1, 2, 3][..]));
+    ///
+    /// let array = IArray::<u8>::Static(&[1, 2, 3]);
+    /// assert!(array.as_rc_slice().is_none());
+    /// ```
+    #[inline]
+    pub fn as_rc_slice(&self) -> Option<Rc<[T]>> {
         match self {
-            Self::Static(a) => a.eq(other),
-            Self::Rc(a) => a.eq(*other),
-            Self::Single(a) if N == 1 => a[0].eq(&other[0]),
-            Self::Single(_) => false,
+            Self::Rc(a) => Some(a.clone()),
+            Self::Static(_) => None,
+            Self::Single(_) => None,
         }
     }
-}
+
+    /// Consumes the array and returns a `'static` slice.
+    ///
+    /// A `Static` array returns its slice without cost. For an `Rc` array, the reference count is
+    /// intentionally leaked so the allocation is never freed, and a `Single` element is moved into
+    /// a leaked [`Box`], like [`Box::leak`]. This is meant for values that live until the end of
+    /// the program, such as long-lived caches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u8>::from(vec![1, 2, 3]);
+    /// let leaked: &'static [u8] = array.leak();
+    /// let array = IArray::<u8>::Static(leaked);
+    /// assert_eq!(array, [1, 2, 3]);
+    /// ```
+    pub fn leak(self) -> &'static [T] {
+        match self {
+            Self::Static(a) => a,
+            // SAFETY: the strong count consumed by `into_raw` is never released, so the
+            // allocation outlives every borrow. The reference only gives shared access to the
+            // elements, like the other clones of the `Rc`.
+            Self::Rc(a) => unsafe { &*Rc::into_raw(a) },
+            Self::Single(a) => Box::leak(Box::new(a)),
+        }
+    }
+
+    /// Returns the static slice if this array is `Static`, or `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u8>::Static(&[1, 2, 3]);
+    /// assert_eq!(array.as_static(), Some(&[1, 2, 3][..]));
+    /// assert!(IArray::<u8>::from(vec![1, 2, 3]).as_static().is_none());
+    /// ```
+    #[inline]
+    pub fn as_static(&self) -> Option<&'static [T]> {
+        match self {
+            Self::Static(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to the backing `Rc` if this array is `Rc`, or `None` otherwise.
+    ///
+    /// See [`as_rc_slice`](Self::as_rc_slice) to get a clone of the `Rc` instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u8>::from(vec![1, 2, 3]);
+    /// assert_eq!(array.as_rc().map(|rc| &rc[..]), Some(&[1, 2, 3][..]));
+    /// assert!(IArray::<u8>::Static(&[1, 2, 3]).as_rc().is_none());
+    /// ```
+    #[inline]
+    pub fn as_rc(&self) -> Option<&Rc<[T]>> {
+        match self {
+            Self::Rc(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to the inner array if this array is `Single`, or `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// assert_eq!(IArray::<u8>::from([1]).as_single(), Some(&[1]));
+    /// assert!(IArray::<u8>::Static(&[1]).as_single().is_none());
+    /// ```
+    #[inline]
+    pub fn as_single(&self) -> Option<&[T; 1]> {
+        match self {
+            Self::Single(a) => Some(a),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of strong references to the backing `Rc`, or 1 if this array is
+    /// `Static` or `Single` since those are never shared.
+    ///
+    /// This is meant for debugging, for example to check that an array is shared rather than
+    /// copied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u8>::from(vec![1, 2, 3]);
+    /// let other = array.clone();
+    /// assert_eq!(array.strong_count(), 2);
+    /// ```
+    #[inline]
+    pub fn strong_count(&self) -> usize {
+        match self {
+            Self::Rc(a) => Rc::strong_count(a),
+            _ => 1,
+        }
+    }
+
+    /// Returns this array in its canonical variant, so that variant checks on arrays of zero or
+    /// one element are predictable.
+    ///
+    /// An empty array becomes [`IArray::EMPTY`] and an array of one element becomes `Single`,
+    /// whatever variant they were built with. A longer `Static` array is already canonical: it is
+    /// kept as-is rather than copied into a new `Rc`, since it neither allocates nor needs a
+    /// reference count. A longer `Rc` array is also returned as-is.
+    ///
+    /// This never allocates. Building a `Single` clones the only element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u8>::from(vec![1]);
+    /// assert!(matches!(array.normalized(), IArray::Single([1])));
+    /// assert!(matches!(IArray::<u8>::Static(&[1]).normalized(), IArray::Single([1])));
+    /// assert!(matches!(IArray::<u8>::from(vec![]).normalized(), IArray::Static(&[])));
+    /// assert!(matches!(IArray::<u8>::Static(&[1, 2]).normalized(), IArray::Static(&[1, 2])));
+    /// ```
+    pub fn normalized(&self) -> IArray<T> {
+        match self.as_slice() {
+            [] => Self::EMPTY,
+            [element] => match self {
+                Self::Single(_) => self.clone(),
+                _ => Self::Single([element.clone()]),
+            },
+            _ => self.clone(),
+        }
+    }
+
+    /// Returns a clone of an element at a position or `None` if out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let v = IArray::<u8>::Static(&[10, 40, 30]);
+    /// assert_eq!(Some(40), v.get(1));
+    /// assert_eq!(None, v.get(3));
+    /// ```
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<T> {
+        match self {
+            Self::Static(a) => a.get(index).cloned(),
+            Self::Rc(a) => a.get(index).cloned(),
+            Self::Single(a) if index == 0 => Some(a[0].clone()),
+            Self::Single(_) => None,
+        }
+    }
+
+    /// Gets a mutable reference into the array, if there are no other references.
+    ///
+    /// If this array is an `Rc` with no other strong or weak references, returns
+    /// a mutable slice of the contained data without any cloning. Otherwise returns
+    /// `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// # use std::rc::Rc;
+    /// // This will reuse the Rc storage
+    /// let mut v1 = IArray::<u8>::Rc(Rc::new([1,2,3]));
+    /// v1.get_mut().unwrap()[1] = 123;
+    /// assert_eq!(&[1,123,3], v1.as_slice());
+    ///
+    /// // Another reference will prevent exclusive access
+    /// let v2 = v1.clone();
+    /// assert!(v1.get_mut().is_none());
+    ///
+    /// // Static references are immutable
+    /// let mut v3 = IArray::<u8>::Static(&[1,2,3]);
+    /// assert!(v3.get_mut().is_none());
+    ///
+    /// // Single items always return a mutable reference
+    /// let mut v4 = IArray::<u8>::Single([1]);
+    /// assert!(v4.get_mut().is_some());
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self) -> Option<&mut [T]> {
+        match self {
+            Self::Rc(ref mut rc) => Rc::get_mut(rc),
+            Self::Static(_) => None,
+            Self::Single(ref mut a) => Some(a),
+        }
+    }
+
+    /// Makes a mutable reference into the array.
+    ///
+    /// If this array is an `Rc` with no other strong or weak references, returns
+    /// a mutable slice of the contained data without any cloning. Otherwise, it clones the
+    /// data into a new array and returns a mutable slice into that.
+    ///
+    /// If this array is a `Static`, it clones its elements into a new `Rc` array and returns a
+    /// mutable slice into that new array.
+    ///
+    /// If this array is a `Single` element, the inner array is returned directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// // This will reuse the Rc storage
+    /// let mut data = IArray::<u8>::from(vec![1,2,3]);
+    /// data.make_mut()[1] = 123;
+    /// assert_eq!(&[1,123,3], data.as_slice());
+    /// assert!(matches!(data, IArray::<u8>::Rc(_)));
+    /// ```
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// // This will create a new copy
+    /// let mut data = IArray::<u8>::from(vec![1,2,3]);
+    /// let other_data = data.clone();
+    /// data.make_mut()[1] = 123;
+    /// assert_eq!(&[1,123,3], data.as_slice());
+    /// assert_eq!(&[1,2,3], other_data.as_slice());
+    /// assert!(matches!(data, IArray::<u8>::Rc(_)));
+    /// assert!(matches!(other_data, IArray::<u8>::Rc(_)));
+    /// ```
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// // This will create a new copy
+    /// let mut data = IArray::<u8>::Static(&[1,2,3]);
+    /// let other_data = data.clone();
+    /// data.make_mut()[1] = 123;
+    /// assert_eq!(&[1,123,3], data.as_slice());
+    /// assert_eq!(&[1,2,3], other_data.as_slice());
+    /// assert!(matches!(data, IArray::<u8>::Rc(_)));
+    /// assert!(matches!(other_data, IArray::<u8>::Static(_)));
+    /// ```
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// // This will use the inner array directly
+    /// let mut data = IArray::<u8>::Single([1]);
+    /// let other_data = data.clone();
+    /// data.make_mut()[0] = 123;
+    /// assert_eq!(&[123], data.as_slice());
+    /// assert_eq!(&[1], other_data.as_slice());
+    /// assert!(matches!(data, IArray::<u8>::Single(_)));
+    /// assert!(matches!(other_data, IArray::<u8>::Single(_)));
+    /// ```
+    #[inline]
+    pub fn make_mut(&mut self) -> &mut [T] {
+        match self {
+            Self::Rc(ref mut rc) => {
+                // This code is somewhat weirdly written to work around
+                // https://github.com/rust-lang/rust/issues/54663 - we can't just check if this is
+                // an Rc with one reference with get_mut in an if branch and copy otherwise, since
+                // returning the mutable slice extends its lifetime for the rest of the function.
+                if Rc::get_mut(rc).is_none() {
+                    *rc = rc.iter().cloned().collect::<Rc<[T]>>();
+                }
+                Rc::get_mut(rc).unwrap()
+            }
+            Self::Static(slice) => {
+                *self = Self::Rc(slice.iter().cloned().collect());
+                match self {
+                    Self::Rc(rc) => Rc::get_mut(rc).unwrap(),
+                    _ => unreachable!(),
+                }
+            }
+            Self::Single(array) => array,
+        }
+    }
+
+    /// Returns mutable references to the elements at each of `indices` at once.
+    ///
+    /// Returns `None` if any index is out of bounds or if an index appears more than once. The
+    /// indices are checked before anything is cloned, otherwise this calls
+    /// [`make_mut`](Self::make_mut) and the array may be copied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let mut array = IArray::<u8>::from(vec![1, 2, 3]);
+    /// if let Some([a, b]) = array.get_disjoint_mut([0, 2]) {
+    ///     std::mem::swap(a, b);
+    /// }
+    /// assert_eq!(array, [3, 2, 1]);
+    /// assert!(array.get_disjoint_mut([1, 1]).is_none());
+    /// ```
+    pub fn get_disjoint_mut<const N: usize>(&mut self, indices: [usize; N]) -> Option<[&mut T; N]> {
+        let len = self.len();
+        for (i, &index) in indices.iter().enumerate() {
+            if index >= len || indices[..i].contains(&index) {
+                return None;
+            }
+        }
+        let ptr = self.make_mut().as_mut_ptr();
+        // SAFETY: every index is in bounds and the indices are pairwise distinct, so the
+        // references do not alias. They borrow `self` mutably for their whole lifetime.
+        Some(indices.map(|index| unsafe { &mut *ptr.add(index) }))
+    }
+
+    /// Converts the array into a [`Box<[T]>`](Box).
+    ///
+    /// If this array is an `Rc` with no other strong or weak references, the elements are moved
+    /// into the box without being cloned. A `Single` element is moved into a one-element box.
+    /// Otherwise the elements are cloned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let data = IArray::<u8>::from(vec![1, 2, 3]);
+    /// let boxed: Box<[u8]> = data.into_boxed_slice();
+    /// assert_eq!(&[1, 2, 3], &*boxed);
+    /// ```
+    pub fn into_boxed_slice(self) -> Box<[T]> {
+        match self {
+            Self::Rc(mut rc) => {
+                if Rc::get_mut(&mut rc).is_none() {
+                    return rc.iter().cloned().collect();
+                }
+                let len = rc.len();
+                let mut vec = Vec::with_capacity(len);
+                let ptr = Rc::into_raw(rc);
+                // SAFETY: the `Rc` was unique so nobody else can observe its elements. They are
+                // moved bitwise into `vec` and the allocation is then released as
+                // `[MaybeUninit<T>]`, which has the same layout as `[T]` but does not drop the
+                // elements a second time.
+                unsafe {
+                    std::ptr::copy_nonoverlapping(ptr as *const T, vec.as_mut_ptr(), len);
+                    vec.set_len(len);
+                    drop(Rc::from_raw(ptr as *const [std::mem::MaybeUninit<T>]));
+                }
+                vec.into_boxed_slice()
+            }
+            Self::Static(a) => a.into(),
+            Self::Single(a) => Box::new(a),
+        }
+    }
+
+    /// Sums the elements of the array by reference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u32>::Static(&[1, 2, 3]);
+    /// assert_eq!(array.sum::<u32>(), 6);
+    /// ```
+    #[inline]
+    pub fn sum<'a, S: std::iter::Sum<&'a T>>(&'a self) -> S {
+        self.as_slice().iter().sum()
+    }
+
+    /// Returns a reference to the minimum element of the array, or `None` if it is empty.
+    ///
+    /// If several elements are equally minimum, the first element is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u32>::Static(&[3, 1, 2]);
+    /// assert_eq!(array.min(), Some(&1));
+    /// assert_eq!(IArray::<u32>::EMPTY.min(), None);
+    /// ```
+    #[inline]
+    pub fn min(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.as_slice().iter().min()
+    }
+
+    /// Returns a reference to the maximum element of the array, or `None` if it is empty.
+    ///
+    /// If several elements are equally maximum, the last element is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u32>::Static(&[3, 1, 2]);
+    /// assert_eq!(array.max(), Some(&3));
+    /// assert_eq!(IArray::<u32>::EMPTY.max(), None);
+    /// ```
+    #[inline]
+    pub fn max(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.as_slice().iter().max()
+    }
+
+    /// Returns a new array with the elements sorted and all the duplicates removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u32>::Static(&[3, 1, 2, 2, 3, 1]);
+    /// assert_eq!(array.sorted_dedup(), [1, 2, 3]);
+    /// ```
+    pub fn sorted_dedup(&self) -> IArray<T>
+    where
+        T: Ord,
+    {
+        let mut vec = self.as_slice().to_vec();
+        vec.sort();
+        vec.dedup();
+        vec.into_iter().collect()
+    }
+
+    /// Returns a new array where consecutive elements considered equal by `same` are removed,
+    /// keeping the first one of each run.
+    ///
+    /// Like [`Vec::dedup_by`], `same` is called with the current element and the last element
+    /// kept before it. If nothing is removed, the array is returned as-is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<i32>::Static(&[1, -1, 2, 3, -3, 3]);
+    /// assert_eq!(array.dedup_by(|a, b| a.abs() == b.abs()), [1, 2, 3]);
+    /// ```
+    pub fn dedup_by<F: FnMut(&T, &T) -> bool>(&self, mut same: F) -> IArray<T> {
+        let mut vec = self.as_slice().to_vec();
+        vec.dedup_by(|a, b| same(a, b));
+        if vec.len() == self.len() {
+            self.clone()
+        } else {
+            IArray::from_vec_exact(vec)
+        }
+    }
+
+    /// Computes an edit script turning this array into `other`, keeping as many elements as
+    /// possible.
+    ///
+    /// The script is based on a longest common subsequence. It computes in **O(n * m)** time and
+    /// allocates a table of `(n + 1) * (m + 1)` integers, so it is only meant for small arrays.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let old = IArray::<char>::Static(&['a', 'b', 'c']);
+    /// let new = IArray::<char>::Static(&['a', 'c', 'd']);
+    ///
+    /// assert_eq!(
+    ///     old.diff(&new),
+    ///     [
+    ///         ArrayEdit::Keep(0),
+    ///         ArrayEdit::Remove(1),
+    ///         ArrayEdit::Keep(2),
+    ///         ArrayEdit::Insert(2, 'd'),
+    ///     ],
+    /// );
+    /// ```
+    pub fn diff(&self, other: &IArray<T>) -> IArray<ArrayEdit<T>>
+    where
+        T: PartialEq,
+    {
+        let (old, new) = (self.as_slice(), other.as_slice());
+        let width = new.len() + 1;
+        // lcs[i * width + j] is the length of the longest common subsequence of old[i..] and
+        // new[j..].
+        let mut lcs = vec![0; (old.len() + 1) * width];
+        for i in (0..old.len()).rev() {
+            for j in (0..new.len()).rev() {
+                lcs[i * width + j] = if old[i] == new[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+
+        let mut edits = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < old.len() && j < new.len() {
+            if old[i] == new[j] {
+                edits.push(ArrayEdit::Keep(i));
+                i += 1;
+                j += 1;
+            } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+                edits.push(ArrayEdit::Remove(i));
+                i += 1;
+            } else {
+                edits.push(ArrayEdit::Insert(j, new[j].clone()));
+                j += 1;
+            }
+        }
+        edits.extend((i..old.len()).map(ArrayEdit::Remove));
+        edits.extend((j..new.len()).map(|j| ArrayEdit::Insert(j, new[j].clone())));
+        IArray::from_vec_exact(edits)
+    }
+
+    /// Returns a new array built by calling `f` on each element with a mutable state.
+    ///
+    /// This mirrors [`Iterator::scan`] except that every element produces a value, so the new
+    /// array has the same length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u32>::Static(&[1, 2, 3]);
+    /// let squares = array.scan(0, |previous, x| {
+    ///     let diff = x * x - *previous;
+    ///     *previous = x * x;
+    ///     diff
+    /// });
+    /// assert_eq!(squares, [1, 3, 5]);
+    /// ```
+    pub fn scan<S, U, F>(&self, init: S, mut f: F) -> IArray<U>
+    where
+        U: ImplicitClone + 'static,
+        F: FnMut(&mut S, T) -> U,
+    {
+        let mut state = init;
+        IArray::from_iter_exact(self.as_slice().iter().map(|x| f(&mut state, x.clone())))
+    }
+
+    /// Returns a new array where each element is the sum of all the elements up to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u32>::Static(&[1, 2, 3]);
+    /// assert_eq!(array.prefix_sum(), [1, 3, 6]);
+    /// ```
+    pub fn prefix_sum(&self) -> IArray<T>
+    where
+        T: std::ops::Add<Output = T> + Default + Copy,
+    {
+        self.scan(T::default(), |total, x| {
+            *total = *total + x;
+            *total
+        })
+    }
+
+    /// Returns a new array with a clone of `sep` inserted between each pair of adjacent
+    /// elements.
+    ///
+    /// If the array has fewer than two elements, it is returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u32>::Static(&[1, 2, 3]);
+    /// assert_eq!(array.intersperse(0), [1, 0, 2, 0, 3]);
+    /// ```
+    pub fn intersperse(&self, sep: T) -> IArray<T> {
+        let slice = self.as_slice();
+        if slice.len() < 2 {
+            return self.clone();
+        }
+
+        let mut vec = Vec::with_capacity(slice.len() * 2 - 1);
+        for (i, x) in slice.iter().enumerate() {
+            if i > 0 {
+                vec.push(sep.clone());
+            }
+            vec.push(x.clone());
+        }
+        IArray::from(vec)
+    }
+
+    /// Returns a new array with `element` inserted at position `index`, shifting all the elements
+    /// after it to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`, like [`Vec::insert`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u32>::Static(&[1, 3]);
+    /// assert_eq!(array.insert(1, 2), [1, 2, 3]);
+    /// ```
+    pub fn insert(&self, index: usize, element: T) -> IArray<T> {
+        let slice = self.as_slice();
+        assert!(
+            index <= slice.len(),
+            "insertion index (is {}) should be <= len (is {})",
+            index,
+            slice.len()
+        );
+        let mut vec = Vec::with_capacity(slice.len() + 1);
+        vec.extend_from_slice(&slice[..index]);
+        vec.push(element);
+        vec.extend_from_slice(&slice[index..]);
+        IArray::from_vec_exact(vec)
+    }
+
+    /// Returns a new array with the element at position `index` removed, shifting all the elements
+    /// after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds, like [`Vec::remove`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u32>::Static(&[1, 2, 3]);
+    /// assert_eq!(array.remove(1), [1, 3]);
+    /// ```
+    pub fn remove(&self, index: usize) -> IArray<T> {
+        let slice = self.as_slice();
+        assert!(
+            index < slice.len(),
+            "removal index (is {}) should be < len (is {})",
+            index,
+            slice.len()
+        );
+        let mut vec = Vec::with_capacity(slice.len() - 1);
+        vec.extend_from_slice(&slice[..index]);
+        vec.extend_from_slice(&slice[index + 1..]);
+        IArray::from_vec_exact(vec)
+    }
+
+    /// Returns an iterator over clones of the elements, in ascending order.
+    ///
+    /// The array is not modified: only a list of indices is sorted, then each element is cloned
+    /// when it is yielded.
+    ///
+    /// Computes in **O(n log n)** time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u32>::Static(&[3, 1, 2]);
+    /// assert_eq!(array.iter_sorted().collect::<Vec<_>>(), [1, 2, 3]);
+    /// assert_eq!(array, [3, 1, 2]);
+    /// ```
+    pub fn iter_sorted(&self) -> impl Iterator<Item = T> + '_
+    where
+        T: Ord,
+    {
+        let slice = self.as_slice();
+        let mut indices = (0..slice.len()).collect::<Vec<_>>();
+        indices.sort_by_key(|&i| &slice[i]);
+        indices.into_iter().map(move |i| slice[i].clone())
+    }
+
+    /// Consumes the array and returns an iterator over the elements paired with their index.
+    ///
+    /// If this array is an `Rc` with no other strong or weak references, or a `Single` element,
+    /// the elements are moved out without being cloned. Otherwise they are cloned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<u8>::from(vec![10, 20]);
+    /// let pairs = array.into_iter_enumerate().collect::<Vec<_>>();
+    /// assert_eq!(pairs, [(0, 10), (1, 20)]);
+    /// ```
+    pub fn into_iter_enumerate(self) -> impl Iterator<Item = (usize, T)> {
+        self.into_boxed_slice().into_vec().into_iter().enumerate()
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// If this array is an `Rc` with no other strong or weak references, the kept elements are
+    /// moved into a new allocation without being cloned. Otherwise the kept elements are cloned
+    /// into a new array and the other references are left untouched.
+    ///
+    /// Computes in **O(n)** time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let mut data = IArray::<u8>::from(vec![1, 2, 3, 4]);
+    /// let other_data = data.clone();
+    /// data.retain(|x| x % 2 == 0);
+    /// assert_eq!(data, [2, 4]);
+    /// assert_eq!(other_data, [1, 2, 3, 4]);
+    /// ```
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut pred: F) {
+        match self {
+            Self::Single([x]) => {
+                if !pred(x) {
+                    *self = Self::EMPTY;
+                }
+            }
+            Self::Rc(rc) if Rc::strong_count(rc) == 1 && Rc::weak_count(rc) == 0 => {
+                let mut vec = std::mem::take(self).into_boxed_slice().into_vec();
+                vec.retain(pred);
+                *self = vec.into_iter().collect();
+            }
+            Self::Rc(_) | Self::Static(_) => {
+                *self = self
+                    .as_slice()
+                    .iter()
+                    .filter(|x| pred(x))
+                    .cloned()
+                    .collect();
+            }
+        }
+    }
+}
+
+impl<T: fmt::Display + ImplicitClone + 'static> IArray<T> {
+    /// Returns a value that displays the elements of the array separated by `sep`.
+    ///
+    /// The elements are written directly to the formatter, no intermediate string is allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<IString>::Static(&[IString::Static("foo"), IString::Static("bar")]);
+    /// assert_eq!(format!("[{}]", array.display_joined(", ")), "[foo, bar]");
+    /// ```
+    pub fn display_joined<'a>(&'a self, sep: &'a str) -> impl fmt::Display + 'a {
+        DisplayJoined {
+            slice: self.as_slice(),
+            sep,
+        }
+    }
+}
+
+struct DisplayJoined<'a, T> {
+    slice: &'a [T],
+    sep: &'a str,
+}
+
+impl<T: fmt::Display> fmt::Display for DisplayJoined<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut it = self.slice.iter();
+        if let Some(first) = it.next() {
+            first.fmt(f)?;
+            for item in it {
+                f.write_str(self.sep)?;
+                item.fmt(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl IArray<super::IString> {
+    /// Concatenates the strings of the array, placing `sep` between each of them.
+    ///
+    /// The exact length of the result is computed first, so the content is written into a buffer
+    /// allocated once and never grown, then copied into the reference counted string. An empty
+    /// array gives an empty string and an array of one string returns a clone of it, without
+    /// allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<IString>::Static(&[IString::Static("foo"), IString::Static("bar")]);
+    /// assert_eq!(array.join_istring(", "), "foo, bar");
+    /// ```
+    pub fn join_istring(&self, sep: &str) -> super::IString {
+        let pieces = self.as_slice();
+        match pieces {
+            [] => super::IString::default(),
+            [piece] => piece.clone(),
+            [first, rest @ ..] => {
+                let len =
+                    pieces.iter().map(|piece| piece.len()).sum::<usize>() + sep.len() * rest.len();
+                let mut buf = String::with_capacity(len);
+                buf.push_str(first);
+                for piece in rest {
+                    buf.push_str(sep);
+                    buf.push_str(piece);
+                }
+                debug_assert_eq!(buf.len(), len);
+                super::IString::Rc(Rc::from(buf))
+            }
+        }
+    }
+
+    /// Concatenates the strings of the array, placing the `IString` `sep` between each of them.
+    ///
+    /// See [`join_istring`](Self::join_istring) for details about allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let array = IArray::<IString>::Static(&[IString::Static("foo"), IString::Static("bar")]);
+    /// let sep = IString::from(" | ");
+    /// assert_eq!(array.join_with(&sep), "foo | bar");
+    /// ```
+    pub fn join_with(&self, sep: &super::IString) -> super::IString {
+        self.join_istring(sep)
+    }
+}
+
+impl<T: PartialEq + ImplicitClone + 'static> PartialEq for IArray<T> {
+    fn eq(&self, other: &Self) -> bool {
+        // Clones share their pointer so the elements don't need to be compared.
+        match (self, other) {
+            (Self::Static(a), Self::Static(b)) => std::ptr::eq(*a, *b) || a == b,
+            (Self::Rc(a), Self::Rc(b)) => Rc::ptr_eq(a, b) || a == b,
+            (Self::Single(a), Self::Single(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<T, U, const N: usize> PartialEq<&[U; N]> for IArray<T>
+where
+    T: PartialEq<U> + ImplicitClone,
+{
+    fn eq(&self, other: &&[U; N]) -> bool {
+        match self {
+            Self::Static(a) => a.eq(other),
+            Self::Rc(a) => a.eq(*other),
+            Self::Single(a) if N == 1 => a[0].eq(&other[0]),
+            Self::Single(_) => false,
+        }
+    }
+}
 
 impl<T, U, const N: usize> PartialEq<[U; N]> for IArray<T>
 where
@@ -380,7 +1485,7 @@ where
     }
 }
 
-impl<'a, T, U> PartialEq<&'a [U]> for IArray<T>
+impl<T, U> PartialEq<&[U]> for IArray<T>
 where
     T: PartialEq<U> + ImplicitClone,
 {
@@ -418,10 +1523,154 @@ impl<'de, T: serde::Deserialize<'de> + ImplicitClone> serde::Deserialize<'de> fo
     }
 }
 
+/// Deserialize an [`IArray`] from either a sequence or a single value.
+///
+/// This module is meant to be used with `#[serde(with = "...")]` on an [`IArray`] field when the
+/// input sometimes provides a lone value where a sequence is expected. A sequence is deserialized
+/// as usual while a single value ends up in a one-element array (`Single`). Serialization always
+/// produces a sequence.
+///
+/// # Examples
+///
+/// ```
+/// # use implicit_clone::unsync::*;
+/// let mut de = serde_json::Deserializer::from_str("[1, 2, 3]");
+/// let array: IArray<u32> = iarray_or_single::deserialize(&mut de).unwrap();
+/// assert_eq!(array, [1, 2, 3]);
+///
+/// let mut de = serde_json::Deserializer::from_str("42");
+/// let array: IArray<u32> = iarray_or_single::deserialize(&mut de).unwrap();
+/// assert_eq!(array, [42]);
+/// ```
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod iarray_or_single {
+    use std::fmt;
+    use std::marker::PhantomData;
+
+    use serde::de::{self, IntoDeserializer};
+
+    use super::IArray;
+    use crate::ImplicitClone;
+
+    /// Serialize the array as a sequence.
+    pub fn serialize<T, S>(array: &IArray<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: serde::Serialize + ImplicitClone + 'static,
+        S: serde::Serializer,
+    {
+        <[T] as serde::Serialize>::serialize(array, serializer)
+    }
+
+    /// Deserialize the array from either a sequence or a single value.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<IArray<T>, D::Error>
+    where
+        T: serde::Deserialize<'de> + ImplicitClone + 'static,
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(OrSingleVisitor(PhantomData))
+    }
+
+    struct OrSingleVisitor<T>(PhantomData<T>);
+
+    macro_rules! forward_single {
+        ($($method:ident($ty:ty)),* $(,)?) => {
+            $(
+                fn $method<E: de::Error>(self, v: $ty) -> Result<Self::Value, E> {
+                    T::deserialize(v.into_deserializer()).map(|element| IArray::Single([element]))
+                }
+            )*
+        };
+    }
+
+    impl<'de, T> de::Visitor<'de> for OrSingleVisitor<T>
+    where
+        T: serde::Deserialize<'de> + ImplicitClone + 'static,
+    {
+        type Value = IArray<T>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a sequence or a single value")
+        }
+
+        forward_single!(
+            visit_bool(bool),
+            visit_i8(i8),
+            visit_i16(i16),
+            visit_i32(i32),
+            visit_i64(i64),
+            visit_i128(i128),
+            visit_u8(u8),
+            visit_u16(u16),
+            visit_u32(u32),
+            visit_u64(u64),
+            visit_u128(u128),
+            visit_f32(f32),
+            visit_f64(f64),
+            visit_char(char),
+            visit_str(&str),
+            visit_string(String),
+        );
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            T::deserialize(de::value::BytesDeserializer::new(v))
+                .map(|element| IArray::Single([element]))
+        }
+
+        fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+            self.visit_bytes(&v)
+        }
+
+        fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+            T::deserialize(de::value::BorrowedStrDeserializer::new(v))
+                .map(|element| IArray::Single([element]))
+        }
+
+        fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+            T::deserialize(de::value::BorrowedBytesDeserializer::new(v))
+                .map(|element| IArray::Single([element]))
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+            T::deserialize(().into_deserializer()).map(|element| IArray::Single([element]))
+        }
+
+        fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+            T::deserialize(de::value::MapAccessDeserializer::new(map))
+                .map(|element| IArray::Single([element]))
+        }
+
+        fn visit_enum<A: de::EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+            T::deserialize(de::value::EnumAccessDeserializer::new(data))
+                .map(|element| IArray::Single([element]))
+        }
+
+        fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+            while let Some(item) = seq.next_element()? {
+                items.push(item);
+            }
+            Ok(IArray::from(items))
+        }
+    }
+}
+
 #[cfg(test)]
 mod test_array {
+    use super::super::IString;
     use super::*;
 
+    static_assertions::const_assert_eq!(
+        std::mem::size_of::<IArray<usize>>(),
+        3 * std::mem::size_of::<usize>()
+    );
+    static_assertions::const_assert_eq!(
+        std::mem::size_of::<IArray<(usize, usize)>>(),
+        3 * std::mem::size_of::<usize>()
+    );
+
+    use crate::test_util::{self as counted, Counted, PanicOnEq};
+
     #[test]
     fn array_in_array() {
         let array_1 = [1, 2, 3].into_iter().collect::<IArray<u32>>();
@@ -465,6 +1714,25 @@ mod test_array {
         const _ARRAY: IArray<u32> = IArray::Static(&[1, 2, 3]);
     }
 
+    #[test]
+    fn as_ptr() {
+        let arrays = [
+            IArray::<u32>::Static(&[1, 2, 3]),
+            IArray::<u32>::from(vec![1, 2, 3]),
+            IArray::<u32>::from([1]),
+            IArray::<u32>::EMPTY,
+        ];
+        for array in arrays {
+            assert_eq!(array.as_ptr(), array.as_slice().as_ptr());
+        }
+    }
+
+    #[test]
+    fn from_static() {
+        const ARRAY: IArray<u32> = IArray::from_static(&[1, 2, 3]);
+        assert!(matches!(ARRAY, IArray::Static(&[1, 2, 3])));
+    }
+
     #[test]
     fn deref_slice() {
         assert!(IArray::Static(&[1, 2, 3]).contains(&1));
@@ -492,4 +1760,758 @@ mod test_array {
         let _array: IArray<u32> = IArray::from(Rc::from(vec![1, 2, 3]));
         let _array: IArray<u32> = IArray::from([1]);
     }
+
+    #[test]
+    fn from_vec_exact() {
+        assert!(matches!(
+            IArray::<u32>::from_vec_exact(Vec::new()),
+            IArray::Static(&[])
+        ));
+        assert!(matches!(
+            IArray::<u32>::from_vec_exact(vec![1]),
+            IArray::Single([1])
+        ));
+        let array = IArray::<u32>::from_vec_exact(vec![1, 2, 3]);
+        assert!(matches!(array, IArray::Rc(_)));
+        assert_eq!(array, [1, 2, 3]);
+
+        let before = counted::clones();
+        let array = IArray::<Counted>::from_vec_exact(vec![Counted(1), Counted(2)]);
+        assert_eq!(counted::clones(), before);
+        assert_eq!(array, [Counted(1), Counted(2)]);
+    }
+
+    #[test]
+    fn from_fn() {
+        let array = IArray::<usize>::from_fn::<4>(|i| i * i);
+        assert_eq!(array, [0, 1, 4, 9]);
+        assert!(matches!(array, IArray::Rc(_)));
+        assert!(matches!(
+            IArray::<usize>::from_fn::<0>(|i| i),
+            IArray::Static(&[])
+        ));
+        assert!(matches!(
+            IArray::<usize>::from_fn::<1>(|i| i + 7),
+            IArray::Single([7])
+        ));
+    }
+
+    #[test]
+    fn generate() {
+        let mut calls = Vec::new();
+        let array = IArray::<u32>::generate(3, |i| {
+            calls.push(i);
+            i as u32 * 10
+        });
+        assert_eq!(array, [0, 10, 20]);
+        assert_eq!(calls, [0, 1, 2]);
+        assert!(matches!(
+            IArray::<u32>::generate(0, |_| 1),
+            IArray::Static(&[])
+        ));
+        assert!(matches!(
+            IArray::<u32>::generate(1, |_| 1),
+            IArray::Single([1])
+        ));
+    }
+
+    #[test]
+    fn capacity() {
+        assert_eq!(IArray::<u32>::EMPTY.capacity(), 0);
+        assert_eq!(IArray::<u32>::Static(&[1, 2, 3]).capacity(), 0);
+        assert_eq!(IArray::<u32>::Single([1]).capacity(), 1);
+
+        let mut vec = Vec::with_capacity(16);
+        vec.extend([1, 2, 3]);
+        assert_eq!(IArray::<u32>::from(vec).capacity(), 3);
+        assert_eq!(IArray::<u32>::Rc(Rc::from([])).capacity(), 0);
+    }
+
+    #[test]
+    fn scan() {
+        let array = IArray::<u32>::from(vec![1, 2, 3, 4]);
+        let labels = array.scan(IString::from("x"), |prefix, x| {
+            *prefix = IString::from(format!("{}{}", prefix, x));
+            prefix.clone()
+        });
+        assert_eq!(labels, ["x1", "x12", "x123", "x1234"]);
+        assert_eq!(IArray::<u32>::EMPTY.scan(0, |_, x| x), IArray::<u32>::EMPTY);
+    }
+
+    #[test]
+    fn prefix_sum() {
+        assert_eq!(IArray::<u32>::Static(&[1, 2, 3]).prefix_sum(), [1, 3, 6]);
+        assert_eq!(IArray::<i32>::from(vec![5, -2, 4]).prefix_sum(), [5, 3, 7]);
+        assert_eq!(IArray::<f64>::from([1.5]).prefix_sum(), [1.5]);
+        assert_eq!(IArray::<u32>::EMPTY.prefix_sum(), IArray::EMPTY);
+    }
+
+    #[test]
+    fn debug_is_consistent_across_variants() {
+        let arrays = [
+            IArray::<u32>::Static(&[1]),
+            IArray::<u32>::from(vec![1]),
+            IArray::<u32>::from([1]),
+        ];
+        for array in &arrays {
+            assert_eq!(format!("{:?}", array), "[1]");
+            assert_eq!(format!("{:#?}", array), "[\n    1,\n]");
+        }
+
+        let arrays = [
+            IArray::<&str>::Static(&["foo", "bar"]),
+            IArray::<&str>::from(vec!["foo", "bar"]),
+        ];
+        for array in &arrays {
+            assert_eq!(format!("{:?}", array), r#"["foo", "bar"]"#);
+            assert_eq!(format!("{:#?}", array), "[\n    \"foo\",\n    \"bar\",\n]");
+        }
+        assert_eq!(format!("{:?}", IArray::<u32>::EMPTY), "[]");
+    }
+
+    #[test]
+    fn variant_accessors() {
+        let array = IArray::<u32>::Static(&[1, 2]);
+        assert_eq!(array.as_static(), Some(&[1, 2][..]));
+        assert!(array.as_rc().is_none());
+        assert!(array.as_single().is_none());
+
+        let rc = Rc::<[u32]>::from(vec![1, 2]);
+        let array = IArray::<u32>::Rc(rc.clone());
+        assert!(array.as_static().is_none());
+        assert!(Rc::ptr_eq(array.as_rc().unwrap(), &rc));
+        assert!(array.as_single().is_none());
+
+        let array = IArray::<u32>::from([1]);
+        assert!(array.as_static().is_none());
+        assert!(array.as_rc().is_none());
+        assert_eq!(array.as_single(), Some(&[1]));
+    }
+
+    #[test]
+    fn chunk_map() {
+        let array = IArray::<u32>::from(vec![1, 2, 3, 4, 5]);
+        assert_eq!(
+            array.chunk_map(2, |chunk| chunk.iter().sum::<u32>()),
+            [3, 7, 5]
+        );
+        assert_eq!(array.chunk_map(5, |chunk| chunk.len()), [5]);
+        assert_eq!(array.chunk_map(10, |chunk| chunk.len()), [5]);
+        assert_eq!(
+            IArray::<u32>::EMPTY.chunk_map(2, |chunk| chunk.len()),
+            IArray::<usize>::EMPTY
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn chunk_map_zero() {
+        IArray::<u32>::Static(&[1]).chunk_map(0, |chunk| chunk.len());
+    }
+
+    #[test]
+    fn cast() {
+        let array = IArray::<u8>::from(vec![1, 2, 255]);
+        let cast: IArray<u32> = array.cast();
+        assert_eq!(cast, [1, 2, 255]);
+        let cast: IArray<i16> = array.cast();
+        assert_eq!(cast, [1, 2, 255]);
+        assert_eq!(IArray::<u8>::EMPTY.cast::<u64>(), IArray::<u64>::EMPTY);
+    }
+
+    #[test]
+    fn try_cast() {
+        let array = IArray::<i32>::from(vec![1, 2, 3]);
+        assert_eq!(array.try_cast::<u8>().unwrap(), [1, 2, 3]);
+        assert_eq!(IArray::<i32>::from(vec![1, -1]).try_cast::<u8>(), None);
+        assert_eq!(
+            IArray::<i32>::EMPTY.try_cast::<u8>(),
+            Some(IArray::<u8>::EMPTY)
+        );
+    }
+
+    #[test]
+    fn iter_sorted() {
+        let array = IArray::<u32>::from(vec![5, 3, 4, 1, 2]);
+        assert_eq!(array.iter_sorted().collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+        assert_eq!(array, [5, 3, 4, 1, 2]);
+
+        let array = IArray::<(u32, &str)>::from(vec![(2, "a"), (1, "b"), (1, "a")]);
+        let sorted = array.iter_sorted().collect::<Vec<_>>();
+        assert_eq!(sorted, [(1, "a"), (1, "b"), (2, "a")]);
+
+        assert_eq!(IArray::<u32>::EMPTY.iter_sorted().count(), 0);
+    }
+
+    #[test]
+    fn leak() {
+        static ITEMS: [u32; 3] = [1, 2, 3];
+        let leaked = IArray::<u32>::Static(&ITEMS).leak();
+        assert!(std::ptr::eq(leaked, &ITEMS[..]));
+
+        let array = IArray::<u32>::from(vec![4, 5]);
+        let other = array.clone();
+        let leaked = array.leak();
+        assert_eq!(leaked, [4, 5]);
+        assert!(std::ptr::eq(leaked, other.as_slice()));
+        drop(other);
+        assert_eq!(leaked, [4, 5]);
+
+        assert_eq!(IArray::<u32>::from([6]).leak(), [6]);
+        assert!(IArray::<u32>::EMPTY.leak().is_empty());
+    }
+
+    #[test]
+    fn intersperse() {
+        let array = IArray::<&str>::Static(&["a", "b", "c"]);
+        assert_eq!(array.intersperse(","), ["a", ",", "b", ",", "c"]);
+
+        let array = IArray::<u32>::from(vec![1, 2]);
+        assert_eq!(array.intersperse(0), [1, 0, 2]);
+
+        let empty = IArray::<u32>::EMPTY;
+        assert!(matches!(empty.intersperse(0), IArray::Static(&[])));
+
+        let single = IArray::<u32>::Static(&[1]);
+        assert!(matches!(single.intersperse(0), IArray::Static(&[1])));
+    }
+
+    #[test]
+    fn concat_all() {
+        let arrays = [
+            IArray::<u32>::Static(&[1, 2]),
+            IArray::from(vec![3, 4, 5]),
+            IArray::from([6]),
+        ];
+        let array = IArray::concat_all(&arrays);
+        assert_eq!(array, [1, 2, 3, 4, 5, 6]);
+        assert!(matches!(array, IArray::Rc(_)));
+
+        assert!(matches!(
+            IArray::<u32>::concat_all(&[]),
+            IArray::Static(&[])
+        ));
+        assert!(matches!(
+            IArray::<u32>::concat_all(&[IArray::EMPTY, IArray::from(vec![])]),
+            IArray::Static(&[])
+        ));
+
+        let rc = IArray::<u32>::from(vec![1, 2]);
+        match IArray::concat_all(&[IArray::EMPTY, rc.clone(), IArray::EMPTY]) {
+            IArray::Rc(a) => assert!(Rc::ptr_eq(&a, rc.as_rc().unwrap())),
+            _ => panic!("expected the non-empty array to be reused"),
+        }
+    }
+
+    #[test]
+    fn collect_results() {
+        let array = [Ok(1), Ok(2), Ok(3)]
+            .into_iter()
+            .collect::<Result<IArray<u32>, &str>>()
+            .unwrap();
+        assert_eq!(array, [1, 2, 3]);
+
+        let single = [Ok::<u32, &str>(1)]
+            .into_iter()
+            .collect::<Result<IArray<u32>, _>>()
+            .unwrap();
+        assert!(matches!(single, IArray::Single([1])));
+
+        let mut seen = Vec::new();
+        let result = [Ok(1), Err("first"), Ok(3), Err("second")]
+            .into_iter()
+            .inspect(|x| seen.push(*x))
+            .collect::<Result<IArray<u32>, &str>>();
+        assert_eq!(result, Err("first"));
+        assert_eq!(seen, [Ok(1), Err("first")]);
+    }
+
+    #[test]
+    fn get_disjoint_mut() {
+        let mut array = IArray::<u32>::from(vec![1, 2, 3, 4]);
+        let [a, b, c] = array.get_disjoint_mut([3, 0, 1]).unwrap();
+        *a += 10;
+        *b += 20;
+        *c += 30;
+        assert_eq!(array, [21, 32, 3, 14]);
+
+        let mut array = IArray::<u32>::Static(&[1, 2, 3]);
+        assert!(array.get_disjoint_mut([0, 2, 0]).is_none());
+        assert!(array.get_disjoint_mut([1, 3]).is_none());
+        assert!(matches!(array, IArray::Static(_)));
+        assert!(IArray::<u32>::default().get_disjoint_mut([0]).is_none());
+
+        let [a] = array.get_disjoint_mut([1]).unwrap();
+        *a = 0;
+        assert_eq!(array, [1, 0, 3]);
+        assert!(matches!(array, IArray::Rc(_)));
+    }
+
+    #[test]
+    fn join_istring() {
+        let pieces = (0..10_000).map(|i| i.to_string()).collect::<Vec<_>>();
+        let array = pieces
+            .iter()
+            .map(|piece| IString::from(piece.clone()))
+            .collect::<IArray<IString>>();
+        assert_eq!(array.join_istring(", "), pieces.join(", "));
+        assert!(matches!(array.join_istring(", "), IString::Rc(_)));
+        assert_eq!(array.join_with(&IString::from("")), pieces.concat());
+        assert_eq!(array.join_with(&IString::from("é")), pieces.join("é"));
+
+        assert_eq!(IArray::<IString>::EMPTY.join_istring(", "), "");
+        let single = IArray::<IString>::from(vec![IString::from(String::from("foo"))]);
+        match (single.join_istring(", "), &single[0]) {
+            (IString::Rc(a), IString::Rc(b)) => assert!(Rc::ptr_eq(&a, b)),
+            _ => panic!("expected the only string to be reused"),
+        }
+
+        // `<[IString]>::join` is still reachable through `Deref`.
+        let joined: String = array[..3].join("-");
+        assert_eq!(joined, "0-1-2");
+    }
+
+    #[test]
+    fn insert() {
+        let array = IArray::<u32>::Static(&[1, 2, 3]);
+        assert_eq!(array.insert(0, 0), [0, 1, 2, 3]);
+        assert_eq!(array.insert(2, 0), [1, 2, 0, 3]);
+        assert_eq!(array.insert(3, 0), [1, 2, 3, 0]);
+        assert_eq!(array, [1, 2, 3]);
+
+        let array = IArray::<u32>::EMPTY.insert(0, 1);
+        assert!(matches!(array, IArray::Single([1])));
+    }
+
+    #[test]
+    #[should_panic(expected = "insertion index (is 4) should be <= len (is 3)")]
+    fn insert_out_of_bounds() {
+        IArray::<u32>::Static(&[1, 2, 3]).insert(4, 0);
+    }
+
+    #[test]
+    fn remove() {
+        let array = IArray::<u32>::from(vec![1, 2, 3]);
+        assert_eq!(array.remove(0), [2, 3]);
+        assert_eq!(array.remove(1), [1, 3]);
+        assert_eq!(array.remove(2), [1, 2]);
+        assert_eq!(array, [1, 2, 3]);
+
+        let array = IArray::<u32>::from([1]).remove(0);
+        assert!(matches!(array, IArray::Static(&[])));
+    }
+
+    #[test]
+    #[should_panic(expected = "removal index (is 0) should be < len (is 0)")]
+    fn remove_out_of_bounds() {
+        IArray::<u32>::EMPTY.remove(0);
+    }
+
+    #[test]
+    fn normalized() {
+        let array = IArray::<u32>::from(vec![1]).normalized();
+        assert!(matches!(array, IArray::Single([1])));
+        assert!(matches!(array.normalized(), IArray::Single([1])));
+        assert!(matches!(
+            IArray::<u32>::Static(&[1]).normalized(),
+            IArray::Single([1])
+        ));
+        assert_eq!(IArray::<u32>::Static(&[1]).normalized(), array);
+
+        assert!(matches!(
+            IArray::<u32>::from(vec![]).normalized(),
+            IArray::Static(&[])
+        ));
+
+        // longer `Static` arrays are canonical and left untouched
+        assert!(matches!(
+            IArray::<u32>::Static(&[1, 2]).normalized(),
+            IArray::Static(&[1, 2])
+        ));
+
+        let array = IArray::<u32>::from(vec![1, 2]);
+        match array.normalized() {
+            IArray::Rc(rc) => assert!(Rc::ptr_eq(&rc, array.as_rc().unwrap())),
+            _ => panic!("expected the Rc to be reused"),
+        }
+    }
+
+    #[test]
+    fn strong_count() {
+        let array = IArray::<u32>::from(vec![1, 2, 3]);
+        assert_eq!(array.strong_count(), 1);
+        let other = array.clone();
+        assert_eq!(array.strong_count(), 2);
+        assert_eq!(other.strong_count(), 2);
+        drop(other);
+        assert_eq!(array.strong_count(), 1);
+
+        let array = IArray::<u32>::Static(&[1, 2, 3]);
+        let _other = array.clone();
+        assert_eq!(array.strong_count(), 1);
+        let array = IArray::<u32>::from([1]);
+        let _other = array.clone();
+        assert_eq!(array.strong_count(), 1);
+    }
+
+    #[test]
+    fn from_slice_owned() {
+        let vec = vec![1, 2, 3];
+        let array = IArray::<u32>::from_slice_owned(&vec);
+        assert!(matches!(array, IArray::Rc(_)));
+        assert_eq!(array, [1, 2, 3]);
+
+        assert!(matches!(
+            IArray::<u32>::from_slice_owned(&vec[..1]),
+            IArray::Single([1])
+        ));
+        assert_eq!(IArray::<u32>::from_slice_owned(&[]), IArray::EMPTY);
+    }
+
+    #[test]
+    fn from_iter_exact() {
+        let array = IArray::<u32>::from_iter_exact(vec![1, 2, 3].into_iter().map(|x| x * 2));
+        assert!(matches!(array, IArray::Rc(_)));
+        assert_eq!(array, [2, 4, 6]);
+
+        let array = IArray::<u32>::from_iter_exact([1].into_iter());
+        assert!(matches!(array, IArray::Single([1])));
+        let array = IArray::<u32>::from_iter_exact(std::iter::empty());
+        assert!(matches!(array, IArray::Static(&[])));
+    }
+
+    #[test]
+    fn from_rc_vec() {
+        let array = IArray::<u32>::from_rc_vec(Rc::new(vec![1, 2, 3]));
+        assert!(matches!(array, IArray::Rc(_)));
+        assert_eq!(array, [1, 2, 3]);
+
+        let rc = Rc::new(vec![1, 2, 3]);
+        let array = IArray::<u32>::from_rc_vec(Rc::clone(&rc));
+        assert_eq!(array, [1, 2, 3]);
+        assert_eq!(*rc, [1, 2, 3]);
+    }
+
+    #[test]
+    fn first_n_last_n() {
+        let array = IArray::<u32>::Static(&[1, 2, 3, 4]);
+        assert!(matches!(array.first_n(2), IArray::Static(&[1, 2])));
+        assert!(matches!(array.last_n(2), IArray::Static(&[3, 4])));
+        assert!(matches!(array.first_n(10), IArray::Static(&[1, 2, 3, 4])));
+        assert!(matches!(array.last_n(10), IArray::Static(&[1, 2, 3, 4])));
+        assert_eq!(array.first_n(0), IArray::EMPTY);
+        assert_eq!(array.last_n(0), IArray::EMPTY);
+
+        let array = IArray::<u32>::from(vec![1, 2, 3, 4]);
+        assert_eq!(array.first_n(3), [1, 2, 3]);
+        assert_eq!(array.last_n(3), [2, 3, 4]);
+        assert!(matches!(array.first_n(1), IArray::Single([1])));
+        assert!(matches!(array.last_n(1), IArray::Single([4])));
+        let whole = array.last_n(4);
+        assert!(matches!((&array, &whole), (IArray::Rc(a), IArray::Rc(b)) if Rc::ptr_eq(a, b)));
+
+        let array = IArray::<u32>::from([1]);
+        assert_eq!(array.first_n(5), [1]);
+        assert_eq!(array.last_n(0), IArray::EMPTY);
+    }
+
+    #[test]
+    fn step_by() {
+        let array = IArray::<u32>::from(vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(array.step_by(2), [0, 2, 4]);
+        assert_eq!(array.step_by(4), [0, 4]);
+        assert!(matches!(array.step_by(6), IArray::Single([0])));
+        assert_eq!(array.step_by(1), array);
+        assert_eq!(IArray::<u32>::EMPTY.step_by(3), IArray::EMPTY);
+    }
+
+    #[test]
+    #[should_panic]
+    fn step_by_zero() {
+        IArray::<u32>::Static(&[1, 2]).step_by(0);
+    }
+
+    #[test]
+    fn as_rc_slice() {
+        let rc = Rc::<[u32]>::from(vec![1, 2, 3]);
+        let array = IArray::Rc(Rc::clone(&rc));
+        assert!(Rc::ptr_eq(&array.as_rc_slice().unwrap(), &rc));
+
+        assert!(IArray::<u32>::Static(&[1, 2, 3]).as_rc_slice().is_none());
+        assert!(IArray::<u32>::Single([1]).as_rc_slice().is_none());
+    }
+
+    #[test]
+    fn into_boxed_slice_does_not_clone_unique_rc() {
+        let clones = counted::clones();
+        let array = IArray::<Counted>::from(vec![Counted(1), Counted(2), Counted(3)]);
+        let boxed = array.into_boxed_slice();
+        assert_eq!(&*boxed, &[Counted(1), Counted(2), Counted(3)]);
+        assert_eq!(counted::clones() - clones, 0);
+
+        let array = IArray::<Counted>::from(vec![Counted(1), Counted(2)]);
+        let _other = array.clone();
+        let boxed = array.into_boxed_slice();
+        assert_eq!(&*boxed, &[Counted(1), Counted(2)]);
+        assert_eq!(counted::clones() - clones, 2);
+
+        let boxed = IArray::from([Counted(4)]).into_boxed_slice();
+        assert_eq!(&*boxed, &[Counted(4)]);
+        assert_eq!(counted::clones() - clones, 2);
+    }
+
+    #[test]
+    fn sum_min_max() {
+        let arrays = [
+            IArray::<u32>::Static(&[3, 1, 4, 1, 5]),
+            IArray::<u32>::from(vec![3, 1, 4, 1, 5]),
+        ];
+        for array in arrays {
+            assert_eq!(array.sum::<u32>(), 14);
+            assert_eq!(array.min(), Some(&1));
+            assert_eq!(array.max(), Some(&5));
+        }
+
+        let array = IArray::<u32>::from([7]);
+        assert_eq!(array.sum::<u32>(), 7);
+        assert_eq!(array.min(), Some(&7));
+        assert_eq!(array.max(), Some(&7));
+
+        let array = IArray::<u32>::EMPTY;
+        assert_eq!(array.sum::<u32>(), 0);
+        assert_eq!(array.min(), None);
+        assert_eq!(array.max(), None);
+    }
+
+    #[test]
+    fn dedup_by() {
+        let words = IArray::<&str>::Static(&["apple", "avocado", "banana", "blueberry", "apricot"]);
+        let same_first_letter = |a: &&str, b: &&str| a.chars().next() == b.chars().next();
+        assert_eq!(
+            words.dedup_by(same_first_letter),
+            ["apple", "banana", "apricot"]
+        );
+
+        let distinct = IArray::<&str>::Static(&["apple", "banana"]);
+        assert!(matches!(
+            distinct.dedup_by(same_first_letter),
+            IArray::Static(&["apple", "banana"])
+        ));
+        assert_eq!(
+            IArray::<&str>::EMPTY.dedup_by(same_first_letter),
+            IArray::EMPTY
+        );
+    }
+
+    fn apply_edits<T: ImplicitClone + 'static>(old: &IArray<T>, edits: &[ArrayEdit<T>]) -> Vec<T> {
+        let mut result = Vec::new();
+        let mut next = 0;
+        for edit in edits {
+            match edit {
+                ArrayEdit::Keep(i) => {
+                    assert_eq!(*i, next);
+                    result.push(old[*i].clone());
+                    next += 1;
+                }
+                ArrayEdit::Insert(j, x) => {
+                    assert_eq!(*j, result.len());
+                    result.push(x.clone());
+                }
+                ArrayEdit::Remove(i) => {
+                    assert_eq!(*i, next);
+                    next += 1;
+                }
+            }
+        }
+        assert_eq!(next, old.len());
+        result
+    }
+
+    #[test]
+    fn diff() {
+        let cases: &[(&[char], &[char])] = &[
+            (&[], &[]),
+            (&[], &['a', 'b']),
+            (&['a', 'b'], &[]),
+            (&['a', 'b', 'c'], &['a', 'b', 'c']),
+            (&['a', 'b', 'c'], &['c', 'b', 'a']),
+            (
+                &['a', 'b', 'c', 'a', 'b', 'b', 'a'],
+                &['c', 'b', 'a', 'b', 'a', 'c'],
+            ),
+            (&['x', 'a', 'y'], &['a', 'z']),
+        ];
+        for (old, new) in cases {
+            let old = IArray::<char>::Static(old);
+            let edits = old.diff(&IArray::Static(new));
+            assert_eq!(apply_edits(&old, &edits), *new);
+        }
+
+        let old = IArray::<char>::Static(&['a', 'b', 'c', 'a', 'b', 'b', 'a']);
+        let new = IArray::<char>::Static(&['c', 'b', 'a', 'b', 'a', 'c']);
+        let kept = old
+            .diff(&new)
+            .iter()
+            .filter(|edit| matches!(edit, ArrayEdit::Keep(_)))
+            .count();
+        assert_eq!(kept, 4);
+
+        let same = IArray::<u32>::from(vec![1, 2, 3]);
+        assert_eq!(
+            same.diff(&same),
+            [ArrayEdit::Keep(0), ArrayEdit::Keep(1), ArrayEdit::Keep(2)]
+        );
+    }
+
+    #[test]
+    fn sorted_dedup() {
+        let array = IArray::<u32>::from(vec![3, 1, 2, 2, 3, 1]);
+        assert_eq!(array.sorted_dedup(), [1, 2, 3]);
+        assert_eq!(array, [3, 1, 2, 2, 3, 1]);
+
+        let array = IArray::<u32>::Static(&[2, 2, 2]).sorted_dedup();
+        assert!(matches!(array, IArray::Single([2])));
+        assert_eq!(IArray::<u32>::EMPTY.sorted_dedup(), IArray::EMPTY);
+    }
+
+    #[test]
+    fn display_joined() {
+        let array = ["foo", "bar", "baz"]
+            .into_iter()
+            .map(IString::from)
+            .collect::<IArray<IString>>();
+        assert_eq!(array.display_joined(", ").to_string(), "foo, bar, baz");
+        assert_eq!(
+            format!("{:>3}", IArray::<u32>::Static(&[1, 2]).display_joined("|")),
+            "  1|  2"
+        );
+        assert_eq!(
+            IArray::<u32>::from([1]).display_joined(", ").to_string(),
+            "1"
+        );
+        assert_eq!(IArray::<u32>::EMPTY.display_joined(", ").to_string(), "");
+    }
+
+    #[test]
+    fn into_iter_enumerate() {
+        let clones = counted::clones();
+        let array = IArray::<Counted>::from(vec![Counted(1), Counted(2), Counted(3)]);
+        let pairs = array.into_iter_enumerate().collect::<Vec<_>>();
+        assert_eq!(pairs, [(0, Counted(1)), (1, Counted(2)), (2, Counted(3))]);
+        assert_eq!(counted::clones() - clones, 0);
+
+        let array = IArray::<Counted>::from(vec![Counted(1), Counted(2), Counted(3)]);
+        let other = array.clone();
+        let pairs = array.into_iter_enumerate().collect::<Vec<_>>();
+        assert_eq!(pairs, [(0, Counted(1)), (1, Counted(2)), (2, Counted(3))]);
+        assert_eq!(counted::clones() - clones, 3);
+        assert_eq!(other.len(), 3);
+
+        let pairs = IArray::<u32>::Static(&[4, 5])
+            .into_iter_enumerate()
+            .collect::<Vec<_>>();
+        assert_eq!(pairs, [(0, 4), (1, 5)]);
+    }
+
+    #[test]
+    fn retain_unique_rc() {
+        let clones = counted::clones();
+        let mut array = IArray::<Counted>::from(vec![Counted(1), Counted(2), Counted(3)]);
+        array.retain(|x| x.0 != 2);
+        assert_eq!(array, [Counted(1), Counted(3)]);
+        assert!(matches!(array, IArray::Rc(_)));
+        assert_eq!(counted::clones() - clones, 0);
+
+        array.retain(|x| x.0 == 3);
+        assert!(matches!(array, IArray::Single(_)));
+        array.retain(|_| false);
+        assert_eq!(array, IArray::EMPTY);
+    }
+
+    #[test]
+    fn retain_shared() {
+        let clones = counted::clones();
+        let mut array = IArray::<Counted>::from(vec![Counted(1), Counted(2), Counted(3)]);
+        let other = array.clone();
+        array.retain(|x| x.0 != 2);
+        assert_eq!(array, [Counted(1), Counted(3)]);
+        assert_eq!(other, [Counted(1), Counted(2), Counted(3)]);
+        assert_eq!(counted::clones() - clones, 2);
+
+        let mut array = IArray::<u32>::Static(&[1, 2, 3, 4]);
+        array.retain(|x| x % 2 == 1);
+        assert_eq!(array, [1, 3]);
+    }
+
+    #[cfg(feature = "map")]
+    #[test]
+    fn group_by_key() {
+        let array = IArray::<u32>::from(vec![1, 2, 3, 4]);
+        let groups = array.group_by_key(|x| if x % 2 == 0 { "even" } else { "odd" });
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            groups.iter().collect::<Vec<_>>(),
+            [
+                ("odd", IArray::from(vec![1, 3])),
+                ("even", IArray::from(vec![2, 4])),
+            ]
+        );
+
+        let groups = IArray::<u32>::EMPTY.group_by_key(|x| *x);
+        assert!(groups.is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn iarray_or_single_deserialize() {
+        let mut de = serde_json::Deserializer::from_str("[1, 2, 3]");
+        let array: IArray<u32> = iarray_or_single::deserialize(&mut de).unwrap();
+        assert_eq!(array, [1, 2, 3]);
+
+        let mut de = serde_json::Deserializer::from_str("7");
+        let array: IArray<u32> = iarray_or_single::deserialize(&mut de).unwrap();
+        assert!(matches!(array, IArray::Single([7])));
+
+        let mut de = serde_json::Deserializer::from_str(r#""foo""#);
+        let array: IArray<IString> = iarray_or_single::deserialize(&mut de).unwrap();
+        assert_eq!(array, ["foo"]);
+        assert!(matches!(array, IArray::Single(_)));
+
+        let mut de = serde_json::Deserializer::from_str(r#"["foo", "bar"]"#);
+        let array: IArray<IString> = iarray_or_single::deserialize(&mut de).unwrap();
+        assert_eq!(array, ["foo", "bar"]);
+
+        let mut de = serde_json::Deserializer::from_str(r#"{"a": 1}"#);
+        assert!(iarray_or_single::deserialize::<u32, _>(&mut de).is_err());
+
+        let mut out = Vec::new();
+        iarray_or_single::serialize(&array, &mut serde_json::Serializer::new(&mut out)).unwrap();
+        assert_eq!(out, br#"["foo","bar"]"#);
+    }
+
+    #[test]
+    fn eq_pointer_fast_path() {
+        static ITEMS: [PanicOnEq; 2] = [PanicOnEq, PanicOnEq];
+        let array = IArray::<PanicOnEq>::Static(&ITEMS);
+        assert_eq!(array, array.clone());
+
+        let array = IArray::<PanicOnEq>::Rc(Rc::from(vec![PanicOnEq, PanicOnEq]));
+        assert_eq!(array, array.clone());
+
+        // equal contents in distinct allocations
+        let a = IArray::<u32>::from(vec![1, 2, 3]);
+        let b = IArray::<u32>::from(vec![1, 2, 3]);
+        assert_eq!(a, b);
+        assert_ne!(a, IArray::<u32>::from(vec![1, 2, 4]));
+        assert_eq!(
+            IArray::<u32>::Static(&[1, 2]),
+            IArray::<u32>::Static(&[1, 2])
+        );
+    }
+
+    #[test]
+    fn into_boxed_slice_static() {
+        let boxed = IArray::<u32>::Static(&[1, 2, 3]).into_boxed_slice();
+        assert_eq!(&*boxed, &[1, 2, 3]);
+    }
 }
@@ -22,8 +22,12 @@
 //!
 //! - [`std::rc::Rc`][std::rc::Rc]
 //! - [`std::sync::Arc`][std::sync::Arc]
-//! - Tuples with 1-12 elements, all of which are also [`ImplicitClone`](crate::ImplicitClone)
+//! - Tuples with 1-16 elements, all of which are also [`ImplicitClone`](crate::ImplicitClone)
 //! - [`Option`][std::option::Option], where inner value is [`ImplicitClone`](crate::ImplicitClone)
+//! - Ranges ([`Range`][std::ops::Range], [`RangeInclusive`][std::ops::RangeInclusive], etc.),
+//!   where the bounds are [`ImplicitClone`](crate::ImplicitClone)
+//! - [`Cow<'static, str>`][std::borrow::Cow] and `Cow<'static, [T]>` (note that cloning the owned
+//!   variant clones its data)
 //! - Some built-in [`Copy`][std::marker::Copy] types, like `()`, `bool`, `&T`, etc.
 //!
 //! This crate is in the category `rust-patterns` but this is actually a Rust anti-pattern. In Rust
@@ -134,12 +138,16 @@
 //! [std::rc::Rc]: https://doc.rust-lang.org/std/rc/struct.Rc.html
 //! [std::sync::Arc]: https://doc.rust-lang.org/std/sync/struct.Arc.html
 //! [std::option::Option]: https://doc.rust-lang.org/stable/std/option/enum.Option.html
+//! [std::borrow::Cow]: https://doc.rust-lang.org/std/borrow/enum.Cow.html
 
 /// Thread-safe version of immutable types.
 pub mod sync;
 /// Single-threaded version of immutable types.
 pub mod unsync;
 
+#[cfg(test)]
+mod test_util;
+
 #[cfg(feature = "implicit-clone-derive")]
 pub use implicit_clone_derive::*;
 
@@ -183,6 +191,24 @@ impl<T: ?Sized> ImplicitClone for &T {}
 
 impl<T: ImplicitClone> ImplicitClone for Option<T> {}
 
+// A `Cow` clones its owned data, which may not be cheap. These implementations exist for
+// compatibility with code that already stores `Cow<'static, _>` values; prefer `IString` and
+// `IArray` for new code.
+impl ImplicitClone for std::borrow::Cow<'static, str> {}
+
+impl<T: Clone> ImplicitClone for std::borrow::Cow<'static, [T]> {}
+
+// Ranges are only as cheap to clone as their bounds.
+impl<T: ImplicitClone> ImplicitClone for std::ops::Range<T> {}
+
+impl<T: ImplicitClone> ImplicitClone for std::ops::RangeInclusive<T> {}
+
+impl<T: ImplicitClone> ImplicitClone for std::ops::RangeFrom<T> {}
+
+impl<T: ImplicitClone> ImplicitClone for std::ops::RangeTo<T> {}
+
+impl ImplicitClone for std::ops::RangeFull {}
+
 macro_rules! impl_implicit_clone {
     ($($ty:ty),+ $(,)?) => {
         $(impl ImplicitClone for $ty {})*
@@ -198,6 +224,11 @@ impl_implicit_clone!(
     usize, isize,
     char,
     (),
+    std::any::TypeId,
+    std::time::SystemTime,
+    std::cmp::Ordering,
+    std::convert::Infallible,
+    std::net::Shutdown,
 );
 
 impl<const N: usize, T: ImplicitClone> ImplicitClone for [T; N] {}
@@ -220,6 +251,12 @@ impl_implicit_clone_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
 impl_implicit_clone_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
 impl_implicit_clone_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
 impl_implicit_clone_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+impl_implicit_clone_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13);
+impl_implicit_clone_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14);
+impl_implicit_clone_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15);
+impl_implicit_clone_for_tuple!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16
+);
 
 /// A macro to help deconstructs maps inspired by JS.
 ///
@@ -243,19 +280,141 @@ impl_implicit_clone_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12
 /// assert_eq!(baz, None);
 /// assert_eq!(foobarbaz, None);
 /// ```
+///
+/// A key can be bound to a variable with another name using `key: name` and a default value can
+/// be provided for missing keys using `key = default`, in which case the variable is not an
+/// `Option`. Both can be combined.
+///
+/// ```rust
+/// use implicit_clone::unsync::*;
+/// use implicit_clone::imap_deconstruct;
+///
+/// let my_imap = [(IString::from("foo"), 1), (IString::from("bar"), 2)]
+///     .into_iter()
+///     .collect::<IMap<IString, u32>>();
+/// imap_deconstruct!(
+///     let { foo: renamed, bar = 0, baz = 3, qux: other = 4 } = my_imap;
+/// );
+/// assert_eq!(renamed, Some(1));
+/// assert_eq!(bar, 2);
+/// assert_eq!(baz, 3);
+/// assert_eq!(other, 4);
+/// ```
 #[cfg(feature = "map")]
 #[cfg_attr(docsrs, doc(cfg(feature = "map")))]
 #[macro_export]
 macro_rules! imap_deconstruct {
-    ($(let { $($key:ident),+ $(,)? } = $map:expr;)*) => {
+    (@bind $map:expr, $key:ident) => {
+        let $key = $map.get_static_str(stringify!($key));
+    };
+    (@bind $map:expr, $key:ident : $rename:ident) => {
+        let $rename = $map.get_static_str(stringify!($key));
+    };
+    (@bind $map:expr, $key:ident = $default:expr) => {
+        let $key = $map.get_static_str(stringify!($key)).unwrap_or_else(|| $default);
+    };
+    (@bind $map:expr, $key:ident : $rename:ident = $default:expr) => {
+        let $rename = $map.get_static_str(stringify!($key)).unwrap_or_else(|| $default);
+    };
+    ($(let { $($key:ident $(: $rename:ident)? $(= $default:expr)?),+ $(,)? } = $map:expr;)*) => {
         $(
         $(
-            let $key = $map.get_static_str(stringify!($key));
+            $crate::imap_deconstruct!(@bind $map, $key $(: $rename)? $(= $default)?);
         )*
         )*
     };
 }
 
+/// A macro to help construct maps inspired by the JS object shorthand.
+///
+/// Every key is the stringified identifier. A key without a value takes the value of the variable
+/// with the same name. The result can be collected into any map that implements
+/// [`FromIterator`], typically an `IMap<IString, V>`.
+///
+/// This macro is an experiment and may change or be entirely deleted before the 1.0 release.
+///
+/// # Usage
+///
+/// ```rust
+/// use implicit_clone::unsync::*;
+/// use implicit_clone::imap_construct;
+///
+/// let bar = 2;
+/// let my_imap: IMap<IString, u32> = imap_construct! { foo: 1, bar };
+/// assert_eq!(my_imap.get("foo"), Some(1));
+/// assert_eq!(my_imap.get("bar"), Some(2));
+/// ```
+#[cfg(feature = "map")]
+#[cfg_attr(docsrs, doc(cfg(feature = "map")))]
+#[macro_export]
+macro_rules! imap_construct {
+    (@value $key:ident) => {
+        $key
+    };
+    (@value $key:ident : $value:expr) => {
+        $value
+    };
+    ($($key:ident $(: $value:expr)?),* $(,)?) => {
+        [$((
+            ::std::convert::From::from(::std::stringify!($key)),
+            $crate::imap_construct!(@value $key $(: $value)?),
+        )),*]
+        .into_iter()
+        .collect()
+    };
+}
+
+/// Creates an `IString` using interpolation of runtime expressions, like [`format!`].
+///
+/// When the input is a single string literal without any interpolation, it expands directly to
+/// the `Static` variant and never allocates. Otherwise it behaves like
+/// `IString::from(format_args!(...))`.
+///
+/// The macro works with both [`sync::IString`](crate::sync::IString) and
+/// [`unsync::IString`](crate::unsync::IString), the target type must be known from the context.
+///
+/// # Usage
+///
+/// ```rust
+/// use implicit_clone::unsync::IString;
+/// use implicit_clone::iformat;
+///
+/// let s: IString = iformat!("Hello World!");
+/// assert!(matches!(s, IString::Static("Hello World!")));
+///
+/// let name = "Jane";
+/// let s: IString = iformat!("Hello {name}!");
+/// assert!(matches!(s, IString::Rc(_)));
+/// assert_eq!(s, "Hello Jane!");
+/// ```
+#[macro_export]
+macro_rules! iformat {
+    ($lit:literal $(,)?) => {{
+        const HAS_BRACES: bool = $crate::__has_format_braces($lit);
+        if HAS_BRACES {
+            ::std::convert::From::from(::std::format_args!($lit))
+        } else {
+            ::std::convert::From::from($lit)
+        }
+    }};
+    ($($arg:tt)*) => {
+        ::std::convert::From::from(::std::format_args!($($arg)*))
+    };
+}
+
+#[doc(hidden)]
+pub const fn __has_format_braces(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'{' || bytes[i] == b'}' {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -290,6 +449,11 @@ mod test {
             (),
             [u8; 4],
             &[u8],
+            std::any::TypeId,
+            std::time::SystemTime,
+            std::cmp::Ordering,
+            std::convert::Infallible,
+            std::net::Shutdown,
         );
     }
 
@@ -305,6 +469,88 @@ mod test {
         assert_not_impl_all!(Option<Vec<u8>>: ImplicitClone);
     }
 
+    #[test]
+    fn cow() {
+        use std::borrow::Cow;
+
+        assert_impl_all!(Cow<'static, str>: ImplicitClone);
+        assert_impl_all!(Cow<'static, [u8]>: ImplicitClone);
+        assert_impl_all!(Cow<'static, [String]>: ImplicitClone);
+    }
+
+    #[test]
+    fn ranges() {
+        use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo};
+
+        assert_impl_all!(Range<u32>: ImplicitClone);
+        assert_impl_all!(RangeInclusive<u32>: ImplicitClone);
+        assert_impl_all!(RangeFrom<usize>: ImplicitClone);
+        assert_impl_all!(RangeTo<usize>: ImplicitClone);
+        assert_impl_all!(RangeFull: ImplicitClone);
+        assert_not_impl_all!(Range<Vec<u8>>: ImplicitClone);
+    }
+
+    #[cfg(feature = "map")]
+    #[test]
+    fn imap_construct_round_trip() {
+        use crate::unsync::{IMap, IString};
+
+        let bar = 2;
+        let map: IMap<IString, u32> = imap_construct! { foo: 1, bar, baz: bar + 1, };
+        assert_eq!(map.len(), 3);
+        imap_deconstruct!(
+            let { foo, bar, baz, qux } = map;
+        );
+        assert_eq!(foo, Some(1));
+        assert_eq!(bar, Some(2));
+        assert_eq!(baz, Some(3));
+        assert_eq!(qux, None);
+
+        let map: IMap<IString, u32> = imap_construct! {};
+        assert!(map.is_empty());
+    }
+
+    #[cfg(feature = "map")]
+    #[test]
+    fn imap_deconstruct_rename_and_default() {
+        use crate::unsync::{IMap, IString};
+
+        let map: IMap<IString, u32> = imap_construct! { foo: 1, bar: 2 };
+        imap_deconstruct!(
+            let { foo, bar: renamed, baz = 3, foo: foo_with_default = 0 } = map;
+            let { missing: other = 5, bar: missing_rename } = map;
+        );
+        assert_eq!(foo, Some(1));
+        assert_eq!(renamed, Some(2));
+        assert_eq!(baz, 3);
+        assert_eq!(foo_with_default, 1);
+        assert_eq!(other, 5);
+        assert_eq!(missing_rename, Some(2));
+    }
+
+    #[test]
+    fn iformat() {
+        use crate::unsync::IString;
+
+        let s: IString = iformat!("x");
+        assert!(matches!(s, IString::Static("x")));
+
+        let x = 42;
+        let s: IString = iformat!("{x}");
+        assert!(matches!(s, IString::Rc(_)));
+        assert_eq!(s, "42");
+
+        let s: IString = iformat!("{}-{}", x, "y");
+        assert!(matches!(s, IString::Rc(_)));
+        assert_eq!(s, "42-y");
+
+        let s: IString = iformat!("{{x}}");
+        assert_eq!(s, "{x}");
+
+        let s: crate::sync::IString = iformat!("x");
+        assert!(matches!(s, crate::sync::IString::Static("x")));
+    }
+
     #[test]
     fn tuples() {
         assert_impl_all!((u8,): ImplicitClone);
@@ -319,7 +565,11 @@ mod test {
         assert_impl_all!((u8, u8, u8, u8, u8, u8, u8, u8, u8, u8): ImplicitClone);
         assert_impl_all!((u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8): ImplicitClone);
         assert_impl_all!((u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8): ImplicitClone);
-        assert_not_impl_all!((u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8): ImplicitClone);
+        assert_impl_all!((u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8): ImplicitClone);
+        assert_impl_all!((u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8): ImplicitClone);
+        assert_impl_all!((u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8): ImplicitClone);
+        assert_impl_all!((u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8): ImplicitClone);
+        assert_not_impl_all!((u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8): ImplicitClone);
         assert_not_impl_all!((Vec<u8>,): ImplicitClone);
     }
 }
@@ -8,6 +8,7 @@ use std::hash::Hash;
 
 use crate::ImplicitClone;
 
+use super::IArray;
 use super::IString;
 use super::Rc;
 
@@ -17,10 +18,13 @@ use super::Rc;
 /// `&'static [(K, V)]`, or based on a reference counted
 /// [`IndexMap`](https://crates.io/crates/indexmap).
 ///
+/// An `IMap` is two words large (the size of a `&[(K, V)]`). This is checked by the tests of this
+/// crate.
+///
 /// This type has the least stable API at the moment and is subject to change a lot before the 1.0
 /// release.
 #[cfg_attr(docsrs, doc(cfg(feature = "map")))]
-#[derive(PartialEq, Eq)]
+#[derive(Eq)]
 pub enum IMap<K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClone + 'static> {
     /// A (small) static map.
     Static(&'static [(K, V)]),
@@ -28,7 +32,6 @@ pub enum IMap<K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClo
     Rc(Rc<Map<K, V>>),
 }
 
-// TODO add insta tests
 impl<
         K: fmt::Debug + Eq + Hash + ImplicitClone + 'static,
         V: fmt::Debug + PartialEq + ImplicitClone + 'static,
@@ -36,8 +39,11 @@ impl<
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Self::Static(a) => a.fmt(f),
-            Self::Rc(a) => a.fmt(f),
+            Self::Static(a) => f
+                .debug_map()
+                .entries(a.iter().map(|(k, v)| (k, v)))
+                .finish(),
+            Self::Rc(a) => f.debug_map().entries(a.iter()).finish(),
         }
     }
 }
@@ -108,9 +114,25 @@ impl<K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClone + 'sta
 }
 
 impl<K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClone + 'static> IMap<K, V> {
+    /// Create a map from a static slice of key-value pairs without allocation.
+    ///
+    /// This is usable in `const` contexts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// const MAP: IMap<&str, u32> = IMap::from_static(&[("foo", 1), ("bar", 2)]);
+    /// assert_eq!(MAP.get("bar"), Some(2));
+    /// ```
+    #[inline]
+    pub const fn from_static(pairs: &'static [(K, V)]) -> Self {
+        Self::Static(pairs)
+    }
+
     /// Return an iterator over the key-value pairs of the map, in their order.
     #[inline]
-    pub fn iter(&self) -> IMapIter<K, V> {
+    pub fn iter(&self) -> IMapIter<'_, K, V> {
         match self {
             Self::Static(a) => IMapIter::Slice(a.iter()),
             Self::Rc(a) => IMapIter::Map(a.iter()),
@@ -119,7 +141,7 @@ impl<K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClone + 'sta
 
     /// Return an iterator over the keys of the map, in their order.
     #[inline]
-    pub fn keys(&self) -> IMapKeys<K, V> {
+    pub fn keys(&self) -> IMapKeys<'_, K, V> {
         match self {
             Self::Static(a) => IMapKeys::Slice(a.iter()),
             Self::Rc(a) => IMapKeys::Map(a.keys()),
@@ -128,7 +150,7 @@ impl<K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClone + 'sta
 
     /// Return an iterator over the values of the map, in their order.
     #[inline]
-    pub fn values(&self) -> IMapValues<K, V> {
+    pub fn values(&self) -> IMapValues<'_, K, V> {
         match self {
             Self::Static(a) => IMapValues::Slice(a.iter()),
             Self::Rc(a) => IMapValues::Map(a.values()),
@@ -146,6 +168,43 @@ impl<K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClone + 'sta
         }
     }
 
+    /// Return the number of key-value pairs the backing map can hold without reallocating.
+    ///
+    /// This is always `0` for a `Static` map. This can help deciding whether it is worth
+    /// rebuilding the map with [`into_builder`](Self::into_builder) and
+    /// [`shrink_to_fit`](indexmap::IndexMap::shrink_to_fit).
+    ///
+    /// Computes in **O(1)** time.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        match self {
+            Self::Static(_) => 0,
+            Self::Rc(a) => a.capacity(),
+        }
+    }
+
+    /// Shrinks the capacity of the backing map as much as possible.
+    ///
+    /// This only applies to an `Rc` map with no other strong or weak references, which is
+    /// shrunk in place. A shared map is left untouched to avoid cloning its entries and a
+    /// `Static` map doesn't allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let mut map = IMap::<u32, u32>::from(indexmap::IndexMap::with_capacity(100));
+    /// map.shrink_to_fit();
+    /// assert_eq!(map.capacity(), 0);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        if let Self::Rc(a) = self {
+            if let Some(map) = Rc::get_mut(a) {
+                map.shrink_to_fit();
+            }
+        }
+    }
+
     /// Returns true if the map contains no elements.
     ///
     /// Computes in **O(1)** time.
@@ -176,6 +235,28 @@ impl<K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClone + 'sta
         }
     }
 
+    /// Return clones to the values stored for each of `keys`, in order.
+    ///
+    /// Each entry is `None` if the corresponding key is not present.
+    ///
+    /// Computes in **O(N)** time (average).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let map = IMap::<IString, u32>::from_iter([("a".into(), 1), ("b".into(), 2)]);
+    /// assert_eq!(map.get_many(["a", "c", "b"]), [Some(1), None, Some(2)]);
+    /// ```
+    #[inline]
+    pub fn get_many<const N: usize, Q>(&self, keys: [&Q; N]) -> [Option<V>; N]
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        keys.map(|key| self.get(key))
+    }
+
     /// Return clones to the key-value pair stored for `key`,
     /// if it is present, else `None`.
     ///
@@ -208,6 +289,22 @@ impl<K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClone + 'sta
         }
     }
 
+    /// Return item index and references to the key and value, without cloning them.
+    #[inline]
+    pub fn get_full_ref<Q>(&self, key: &Q) -> Option<(usize, &K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self {
+            Self::Static(a) => a
+                .iter()
+                .enumerate()
+                .find_map(|(i, (k, v))| (k.borrow() == key).then(|| (i, k, v))),
+            Self::Rc(a) => a.get_full(key),
+        }
+    }
+
     /// Get a key-value pair by index.
     ///
     /// Valid indices are *0 <= index < self.len()*
@@ -221,6 +318,19 @@ impl<K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClone + 'sta
         }
     }
 
+    /// Get references to a key-value pair by index, without cloning them.
+    ///
+    /// Valid indices are *0 <= index < self.len()*
+    ///
+    /// Computes in **O(1)** time.
+    #[inline]
+    pub fn get_index_ref(&self, index: usize) -> Option<(&K, &V)> {
+        match self {
+            Self::Static(a) => a.get(index).map(|(k, v)| (k, v)),
+            Self::Rc(a) => a.get_index(index),
+        }
+    }
+
     /// Return item index, if it exists in the map.
     ///
     /// Computes in **O(1)** time (average).
@@ -264,6 +374,589 @@ impl<K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClone + 'sta
             Self::Rc(a) => a.last().map(|(k, v)| (k.clone(), v.clone())),
         }
     }
+
+    /// Fold every key-value pair of the map, in their order, into an accumulator.
+    ///
+    /// The entries are passed by reference and are not cloned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let map = IMap::<&str, u32>::Static(&[("a", 1), ("b", 2)]);
+    /// assert_eq!(map.fold(String::new(), |acc, (k, v)| format!("{acc}{k}{v}")), "a1b2");
+    /// ```
+    #[inline]
+    pub fn fold<B, F: FnMut(B, (&K, &V)) -> B>(&self, init: B, f: F) -> B {
+        match self {
+            Self::Static(a) => a.iter().map(|(k, v)| (k, v)).fold(init, f),
+            Self::Rc(a) => a.iter().fold(init, f),
+        }
+    }
+
+    /// Fold every value of the map, in their order, into an accumulator.
+    ///
+    /// The values are passed by reference and are not cloned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let map = IMap::<&str, u32>::Static(&[("a", 1), ("b", 2), ("c", 3)]);
+    /// assert_eq!(map.fold_values(0, |acc, v| acc.max(*v)), 3);
+    /// ```
+    #[inline]
+    pub fn fold_values<B, F: FnMut(B, &V) -> B>(&self, init: B, f: F) -> B {
+        match self {
+            Self::Static(a) => a.iter().map(|(_, v)| v).fold(init, f),
+            Self::Rc(a) => a.values().fold(init, f),
+        }
+    }
+
+    /// Sum every value of the map.
+    ///
+    /// The values are passed by reference and are not cloned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let map = IMap::<&str, u32>::Static(&[("a", 1), ("b", 2), ("c", 3)]);
+    /// assert_eq!(map.sum_values(), 6);
+    /// ```
+    #[inline]
+    pub fn sum_values(&self) -> V
+    where
+        V: for<'a> std::iter::Sum<&'a V>,
+    {
+        match self {
+            Self::Static(a) => a.iter().map(|(_, v)| v).sum(),
+            Self::Rc(a) => a.values().sum(),
+        }
+    }
+
+    /// Join this map with `other` on their keys.
+    ///
+    /// The resulting map contains every key of both maps, associated with the value of each map
+    /// if present. Keys of `self` come first, in their order, followed by the keys that only exist
+    /// in `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let a = IMap::<&str, u32>::Static(&[("foo", 1), ("bar", 2)]);
+    /// let b = IMap::<&str, bool>::Static(&[("bar", true), ("baz", false)]);
+    /// let joined = a.join(&b);
+    /// assert_eq!(
+    ///     joined.iter().collect::<Vec<_>>(),
+    ///     [
+    ///         ("foo", (Some(1), None)),
+    ///         ("bar", (Some(2), Some(true))),
+    ///         ("baz", (None, Some(false))),
+    ///     ],
+    /// );
+    /// ```
+    pub fn join<W: PartialEq + ImplicitClone + 'static>(
+        &self,
+        other: &IMap<K, W>,
+    ) -> IMap<K, (Option<V>, Option<W>)> {
+        let mut map = Map::with_capacity(self.len());
+        for (k, v) in self.iter() {
+            map.insert(k, (Some(v), None));
+        }
+        for (k, w) in other.iter() {
+            map.entry(k).or_insert((None, None)).1 = Some(w);
+        }
+        IMap::Rc(Rc::new(map))
+    }
+
+    /// Return an iterator over the key-value pairs of this map whose keys are not in `other`, in
+    /// their order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let a = IMap::<&str, u32>::Static(&[("foo", 1), ("bar", 2)]);
+    /// let b = IMap::<&str, u32>::Static(&[("bar", 3), ("baz", 4)]);
+    /// assert_eq!(a.difference(&b).collect::<Vec<_>>(), [("foo", 1)]);
+    /// ```
+    pub fn difference<'a>(&'a self, other: &'a IMap<K, V>) -> impl Iterator<Item = (K, V)> + 'a {
+        self.iter().filter(move |(k, _)| !other.contains_key(k))
+    }
+
+    /// Return an iterator over the key-value pairs whose keys are in only one of the two maps.
+    ///
+    /// The entries of this map come first, followed by the entries of `other`, in their order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let a = IMap::<&str, u32>::Static(&[("foo", 1), ("bar", 2)]);
+    /// let b = IMap::<&str, u32>::Static(&[("bar", 3), ("baz", 4)]);
+    /// assert_eq!(
+    ///     a.symmetric_difference(&b).collect::<Vec<_>>(),
+    ///     [("foo", 1), ("baz", 4)],
+    /// );
+    /// ```
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a IMap<K, V>,
+    ) -> impl Iterator<Item = (K, V)> + 'a {
+        self.difference(other).chain(other.difference(self))
+    }
+
+    /// Return a new map where the key `old` is replaced by `new`, keeping its position.
+    ///
+    /// If `old` is not in the map, an equal clone is returned. If `new` is already a key of the
+    /// map, its entry is overwritten: it is removed and the renamed entry keeps the position of
+    /// `old`.
+    ///
+    /// Computes in **O(n)** time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let map = IMap::<&str, u32>::Static(&[("foo", 1), ("bar", 2)]);
+    /// let renamed = map.rename_key(&"foo", "baz");
+    /// assert_eq!(renamed.iter().collect::<Vec<_>>(), [("baz", 1), ("bar", 2)]);
+    /// ```
+    pub fn rename_key(&self, old: &K, new: K) -> IMap<K, V> {
+        if *old == new || !self.contains_key(old) {
+            return self.clone();
+        }
+        self.iter()
+            .filter(|(k, _)| *k != new)
+            .map(|(k, v)| if k == *old { (new.clone(), v) } else { (k, v) })
+            .collect()
+    }
+
+    /// Return an iterator over the entries of the map, in ascending key order.
+    ///
+    /// The map is not modified: only a list of indices is sorted, then each entry is cloned when
+    /// it is yielded.
+    ///
+    /// Computes in **O(n log n)** time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let map = IMap::<u32, &str>::Static(&[(3, "c"), (1, "a"), (2, "b")]);
+    /// assert_eq!(
+    ///     map.iter_sorted().collect::<Vec<_>>(),
+    ///     [(1, "a"), (2, "b"), (3, "c")],
+    /// );
+    /// ```
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (K, V)> + '_
+    where
+        K: Ord,
+    {
+        let mut indices = (0..self.len()).collect::<Vec<_>>();
+        indices.sort_unstable_by_key(|&i| self.get_index_ref(i).map(|(k, _)| k));
+        indices.into_iter().filter_map(move |i| self.get_index(i))
+    }
+
+    /// Return a new map with `key` inserted at the end, or the existing value if `key` is already
+    /// present.
+    ///
+    /// This never overwrites an entry, in the spirit of `HashMap::try_insert`. The map itself is
+    /// left untouched.
+    ///
+    /// Computes in **O(n)** time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let map = IMap::<&str, u32>::Static(&[("foo", 1)]);
+    /// let map = map.try_insert("bar", 2).unwrap();
+    /// assert_eq!(map.iter().collect::<Vec<_>>(), [("foo", 1), ("bar", 2)]);
+    /// assert_eq!(map.try_insert("foo", 3), Err(1));
+    /// ```
+    pub fn try_insert(&self, key: K, value: V) -> Result<IMap<K, V>, V> {
+        if let Some(existing) = self.get(&key) {
+            return Err(existing);
+        }
+        Ok(self.iter().chain(std::iter::once((key, value))).collect())
+    }
+
+    /// Return the map with `key` present and a clone of its value, calling `f` to compute the
+    /// value only if `key` is absent.
+    ///
+    /// If `key` is already present, the returned map is a clone of this one. Otherwise it is a new
+    /// map with the entry inserted at the end, like [`try_insert`](Self::try_insert).
+    ///
+    /// Computes in **O(n)** time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let cache = IMap::<u32, u32>::default();
+    /// let (cache, square) = cache.get_or_insert_with(3, || 3 * 3);
+    /// assert_eq!(square, 9);
+    ///
+    /// let (cache, square) = cache.get_or_insert_with(3, || unreachable!());
+    /// assert_eq!(square, 9);
+    /// assert_eq!(cache.len(), 1);
+    /// ```
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&self, key: K, f: F) -> (IMap<K, V>, V) {
+        match self.get(&key) {
+            Some(value) => (self.clone(), value),
+            None => {
+                let value = f();
+                let map = self
+                    .iter()
+                    .chain(std::iter::once((key, value.clone())))
+                    .collect();
+                (map, value)
+            }
+        }
+    }
+
+    /// Return a new map with the entries of `self` followed by the entries of `defaults` whose key
+    /// is not in `self`.
+    ///
+    /// The entries of `self` win over the defaults. If every default key is already present, an
+    /// equal clone is returned without allocating.
+    ///
+    /// Computes in **O(n + m)** time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// static DEFAULTS: &[(&str, u32)] = &[("width", 100), ("height", 50)];
+    /// let map = [("height", 80)].into_iter().collect::<IMap<&str, u32>>();
+    /// let map = map.with_defaults(DEFAULTS);
+    /// assert_eq!(map.iter().collect::<Vec<_>>(), [("height", 80), ("width", 100)]);
+    /// ```
+    pub fn with_defaults(&self, defaults: &'static [(K, V)]) -> IMap<K, V> {
+        if defaults.iter().all(|(k, _)| self.contains_key(k)) {
+            return self.clone();
+        }
+        self.iter()
+            .chain(
+                defaults
+                    .iter()
+                    .filter(|(k, _)| !self.contains_key(k))
+                    .cloned(),
+            )
+            .collect()
+    }
+
+    /// Return a new map with only the entries for which `pred` returns `true`, preserving the
+    /// order.
+    ///
+    /// The entries are passed by reference and only the retained ones are cloned. If every entry
+    /// is retained, an equal clone is returned without allocating.
+    ///
+    /// Computes in **O(n)** time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let map = IMap::<&str, u32>::Static(&[("a", 1), ("b", 2), ("c", 3)]);
+    /// let map = map.filtered(|k, v| *k != "a" && *v < 3);
+    /// assert_eq!(map.iter().collect::<Vec<_>>(), [("b", 2)]);
+    /// ```
+    pub fn filtered<F: FnMut(&K, &V) -> bool>(&self, mut pred: F) -> IMap<K, V> {
+        let kept = self.fold(Map::new(), |mut kept, (k, v)| {
+            if pred(k, v) {
+                kept.insert(k.clone(), v.clone());
+            }
+            kept
+        });
+        if kept.len() == self.len() {
+            self.clone()
+        } else if kept.is_empty() {
+            IMap::default()
+        } else {
+            IMap::from(kept)
+        }
+    }
+
+    /// Return a new map with only the entries whose key satisfies `pred`.
+    ///
+    /// See [`filtered`](Self::filtered) for details about allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let map = IMap::<&str, u32>::Static(&[("data-id", 1), ("class", 2)]);
+    /// let map = map.filtered_keys(|k| k.starts_with("data-"));
+    /// assert_eq!(map.keys().collect::<Vec<_>>(), ["data-id"]);
+    /// ```
+    pub fn filtered_keys<F: FnMut(&K) -> bool>(&self, mut pred: F) -> IMap<K, V> {
+        self.filtered(|k, _| pred(k))
+    }
+
+    /// Return a new map with only the entries whose value satisfies `pred`.
+    ///
+    /// See [`filtered`](Self::filtered) for details about allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let map = IMap::<&str, u32>::Static(&[("a", 1), ("b", 20)]);
+    /// let map = map.filtered_values(|v| *v >= 10);
+    /// assert_eq!(map.keys().collect::<Vec<_>>(), ["b"]);
+    /// ```
+    pub fn filtered_values<F: FnMut(&V) -> bool>(&self, mut pred: F) -> IMap<K, V> {
+        self.filtered(|_, v| pred(v))
+    }
+
+    /// Return a new map where every key is transformed by `f`, preserving the order.
+    ///
+    /// If `f` maps several keys to the same new key, the value of the last one wins and the entry
+    /// keeps the position of the first one.
+    ///
+    /// Computes in **O(n)** time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let map = IMap::<IString, u32>::Static(&[(IString::Static("foo"), 1)]);
+    /// let prefixed = map.map_keys(|k| IString::from(format!("data-{}", k)));
+    /// assert_eq!(prefixed.get("data-foo"), Some(1));
+    /// ```
+    pub fn map_keys<L, F>(&self, mut f: F) -> IMap<L, V>
+    where
+        L: Eq + Hash + ImplicitClone + 'static,
+        F: FnMut(K) -> L,
+    {
+        self.iter().map(|(k, v)| (f(k), v)).collect()
+    }
+
+    /// Convert the map into an [`IMapBuilder`] that can be mutated in place.
+    ///
+    /// If this map is an `Rc` with no other strong or weak references, the underlying
+    /// [`IndexMap`](https://crates.io/crates/indexmap) is reused without being cloned. Otherwise
+    /// its entries are cloned once into a new map.
+    ///
+    /// This is useful to apply many updates in a row and only pay for a single allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let map = IMap::<u32, u32>::Static(&[(0, 0)]);
+    /// let mut builder = map.into_builder();
+    /// for i in 1..10 {
+    ///     builder.insert(i, i * 2);
+    /// }
+    /// let map = builder.build();
+    /// assert_eq!(map.len(), 10);
+    /// assert_eq!(map.get(&9), Some(18));
+    /// ```
+    pub fn into_builder(self) -> IMapBuilder<K, V> {
+        let map = match self {
+            Self::Static(a) => a.iter().cloned().collect(),
+            Self::Rc(a) => Rc::try_unwrap(a).unwrap_or_else(|a| (*a).clone()),
+        };
+        IMapBuilder { map }
+    }
+
+    /// Return a new map where the entries at indices `a` and `b` are swapped.
+    ///
+    /// The entries are cloned into a new map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` are out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let map = IMap::<&str, u32>::Static(&[("a", 1), ("b", 2), ("c", 3)]);
+    /// let map = map.swap_indices(0, 2);
+    /// assert_eq!(map.keys().collect::<Vec<_>>(), ["c", "b", "a"]);
+    /// ```
+    pub fn swap_indices(&self, a: usize, b: usize) -> IMap<K, V> {
+        let mut builder = self.clone().into_builder();
+        builder.swap_indices(a, b);
+        builder.build()
+    }
+
+    /// Return a new map where the entry at index `from` is moved to index `to`, shifting the
+    /// entries in between.
+    ///
+    /// The entries are cloned into a new map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from` or `to` are out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let map = IMap::<&str, u32>::Static(&[("a", 1), ("b", 2), ("c", 3)]);
+    /// let map = map.move_index(0, 2);
+    /// assert_eq!(map.keys().collect::<Vec<_>>(), ["b", "c", "a"]);
+    /// ```
+    pub fn move_index(&self, from: usize, to: usize) -> IMap<K, V> {
+        let mut builder = self.clone().into_builder();
+        builder.move_index(from, to);
+        builder.build()
+    }
+
+    /// Consumes the map and returns an iterator over its keys, in order.
+    ///
+    /// If this map is an `Rc` with no other strong references, the keys are moved out of the
+    /// underlying [`IndexMap`](https://crates.io/crates/indexmap). Otherwise each key is cloned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let map = [(IString::from("foo"), 1), (IString::from("bar"), 2)]
+    ///     .into_iter()
+    ///     .collect::<IMap<IString, u32>>();
+    /// assert_eq!(map.into_keys().collect::<Vec<_>>(), ["foo", "bar"]);
+    /// ```
+    pub fn into_keys(self) -> impl Iterator<Item = K> {
+        self.into_owned_iter(|(k, _)| k, |(k, _)| k)
+    }
+
+    /// Consumes the map and returns an iterator over its values, in order.
+    ///
+    /// If this map is an `Rc` with no other strong references, the values are moved out of the
+    /// underlying [`IndexMap`](https://crates.io/crates/indexmap). Otherwise each value is cloned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let map = [(IString::from("foo"), 1), (IString::from("bar"), 2)]
+    ///     .into_iter()
+    ///     .collect::<IMap<IString, u32>>();
+    /// assert_eq!(map.into_values().collect::<Vec<_>>(), [1, 2]);
+    /// ```
+    pub fn into_values(self) -> impl Iterator<Item = V> {
+        self.into_owned_iter(|(_, v)| v, |(_, v)| v)
+    }
+
+    /// Compute the changes needed to go from this map to `other`.
+    ///
+    /// The additions and changes come first, in the order of the keys of `other`, followed by the
+    /// removals, in the order of the keys of this map. The order of the entries themselves is not
+    /// compared.
+    ///
+    /// Computes in **O(n + m)** time (average).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let before = IMap::<&str, u32>::Static(&[("a", 1), ("b", 2)]);
+    /// let after = IMap::<&str, u32>::Static(&[("b", 3), ("c", 4)]);
+    ///
+    /// assert_eq!(
+    ///     before.diff(&after),
+    ///     [
+    ///         MapChange::Changed("b", 2, 3),
+    ///         MapChange::Added("c", 4),
+    ///         MapChange::Removed("a"),
+    ///     ],
+    /// );
+    /// ```
+    pub fn diff(&self, other: &IMap<K, V>) -> IArray<MapChange<K, V>> {
+        let mut changes = Vec::new();
+        for (key, new) in other.iter() {
+            match self.get(&key) {
+                Some(old) if old == new => {}
+                Some(old) => changes.push(MapChange::Changed(key, old, new)),
+                None => changes.push(MapChange::Added(key, new)),
+            }
+        }
+        for key in self.keys() {
+            if !other.contains_key(&key) {
+                changes.push(MapChange::Removed(key));
+            }
+        }
+        IArray::from_vec_exact(changes)
+    }
+
+    /// Consumes the map and returns an iterator over one part of each entry, in order.
+    ///
+    /// The entries are moved out of an `Rc` map with no other strong references and `take` picks
+    /// the part to keep. Otherwise `pick` borrows that part of each entry, which is then cloned.
+    fn into_owned_iter<T: Clone>(
+        self,
+        take: fn((K, V)) -> T,
+        pick: for<'a> fn((&'a K, &'a V)) -> &'a T,
+    ) -> impl Iterator<Item = T> {
+        let (unique, shared) = match self {
+            Self::Rc(a) => match Rc::try_unwrap(a) {
+                Ok(map) => (Some(map), None),
+                Err(a) => (None, Some(Self::Rc(a))),
+            },
+            map => (None, Some(map)),
+        };
+        let moved = unique.into_iter().flatten().map(take);
+        let cloned = shared.into_iter().flat_map(move |map| {
+            (0..map.len()).filter_map(move |i| map.get_index_ref(i).map(pick).cloned())
+        });
+        moved.chain(cloned)
+    }
+}
+
+/// A change between two [`IMap`]s, as returned by [`IMap::diff`].
+#[cfg_attr(docsrs, doc(cfg(feature = "map")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MapChange<K, V> {
+    /// The key is only in the new map, with the given value.
+    Added(K, V),
+    /// The key is only in the old map.
+    Removed(K),
+    /// The value of the key changed from the first value to the second one.
+    Changed(K, V, V),
+}
+
+impl<K: ImplicitClone, V: ImplicitClone> ImplicitClone for MapChange<K, V> {}
+
+/// A mutable builder for an [`IMap`].
+///
+/// It dereferences to the underlying [`IndexMap`](https://crates.io/crates/indexmap) so it can be
+/// updated with its whole API before being turned back into an [`IMap`] with
+/// [`build`](Self::build).
+#[cfg_attr(docsrs, doc(cfg(feature = "map")))]
+#[derive(Debug, Clone)]
+pub struct IMapBuilder<K, V> {
+    map: Map<K, V>,
+}
+
+impl<K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClone + 'static>
+    IMapBuilder<K, V>
+{
+    /// Build the [`IMap`] without cloning the entries.
+    pub fn build(self) -> IMap<K, V> {
+        IMap::Rc(Rc::new(self.map))
+    }
+}
+
+impl<K, V> std::ops::Deref for IMapBuilder<K, V> {
+    type Target = Map<K, V>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+impl<K, V> std::ops::DerefMut for IMapBuilder<K, V> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.map
+    }
 }
 
 impl<V: PartialEq + ImplicitClone + 'static> IMap<IString, V> {
@@ -325,6 +1018,36 @@ impl<'a, K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClone +
             Self::Map(it) => it.next().cloned(),
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Slice(it) => it.size_hint(),
+            Self::Map(it) => it.size_hint(),
+        }
+    }
+}
+
+impl<'a, K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClone + 'static>
+    DoubleEndedIterator for IMapKeys<'a, K, V>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Slice(it) => it.next_back().map(|(k, _)| k.clone()),
+            Self::Map(it) => it.next_back().cloned(),
+        }
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        match self {
+            Self::Slice(it) => it.nth_back(n).map(|(k, _)| k.clone()),
+            Self::Map(it) => it.nth_back(n).cloned(),
+        }
+    }
+}
+
+impl<'a, K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClone + 'static>
+    ExactSizeIterator for IMapKeys<'a, K, V>
+{
 }
 
 #[allow(missing_docs, missing_debug_implementations)]
@@ -344,21 +1067,111 @@ impl<'a, K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClone +
             Self::Map(it) => it.next().cloned(),
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Slice(it) => it.size_hint(),
+            Self::Map(it) => it.size_hint(),
+        }
+    }
 }
 
-#[cfg(feature = "serde")]
-impl<K, V> serde::Serialize for IMap<K, V>
-where
-    K: Eq + Hash + ImplicitClone + 'static + serde::Serialize,
-    V: PartialEq + ImplicitClone + 'static + serde::Serialize,
+impl<'a, K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClone + 'static>
+    DoubleEndedIterator for IMapValues<'a, K, V>
 {
-    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        use serde::ser::SerializeMap;
-        let mut seq = serializer.serialize_map(Some(self.len()))?;
+    fn next_back(&mut self) -> Option<Self::Item> {
         match self {
-            Self::Static(a) => {
-                for (k, v) in a.iter() {
-                    seq.serialize_entry(k, v)?;
+            Self::Slice(it) => it.next_back().map(|(_, v)| v.clone()),
+            Self::Map(it) => it.next_back().cloned(),
+        }
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        match self {
+            Self::Slice(it) => it.nth_back(n).map(|(_, v)| v.clone()),
+            Self::Map(it) => it.nth_back(n).cloned(),
+        }
+    }
+}
+
+impl<'a, K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClone + 'static>
+    ExactSizeIterator for IMapValues<'a, K, V>
+{
+}
+
+impl<K: Eq + Hash + ImplicitClone + 'static, V: PartialEq + ImplicitClone + 'static> PartialEq
+    for IMap<K, V>
+{
+    fn eq(&self, other: &Self) -> bool {
+        // Clones share their pointer so the entries don't need to be compared.
+        match (self, other) {
+            (Self::Static(a), Self::Static(b)) => std::ptr::eq(*a, *b) || a == b,
+            (Self::Rc(a), Self::Rc(b)) => Rc::ptr_eq(a, b) || a == b,
+            _ => false,
+        }
+    }
+}
+
+/// Compares the entries of both maps in order: the same entries in a different order are not
+/// equal.
+impl<K, V, S> PartialEq<Map<K, V, S>> for IMap<K, V>
+where
+    K: Eq + Hash + ImplicitClone + 'static,
+    V: PartialEq + ImplicitClone + 'static,
+{
+    fn eq(&self, other: &Map<K, V, S>) -> bool {
+        self.len() == other.len()
+            && match self {
+                Self::Static(a) => a.iter().map(|(k, v)| (k, v)).eq(other.iter()),
+                Self::Rc(a) => a.iter().eq(other.iter()),
+            }
+    }
+}
+
+/// Compares the entries of both maps regardless of their order.
+impl<K, V, S> PartialEq<std::collections::HashMap<K, V, S>> for IMap<K, V>
+where
+    K: Eq + Hash + ImplicitClone + 'static,
+    V: PartialEq + ImplicitClone + 'static,
+    S: std::hash::BuildHasher,
+{
+    fn eq(&self, other: &std::collections::HashMap<K, V, S>) -> bool {
+        self.len() == other.len()
+            && match self {
+                Self::Static(a) => a.iter().all(|(k, v)| other.get(k) == Some(v)),
+                Self::Rc(a) => a.iter().all(|(k, v)| other.get(k) == Some(v)),
+            }
+    }
+}
+
+/// Compares the entries of both maps regardless of their order.
+impl<K, V> PartialEq<std::collections::BTreeMap<K, V>> for IMap<K, V>
+where
+    K: Ord + Hash + ImplicitClone + 'static,
+    V: PartialEq + ImplicitClone + 'static,
+{
+    fn eq(&self, other: &std::collections::BTreeMap<K, V>) -> bool {
+        self.len() == other.len()
+            && match self {
+                Self::Static(a) => a.iter().all(|(k, v)| other.get(k) == Some(v)),
+                Self::Rc(a) => a.iter().all(|(k, v)| other.get(k) == Some(v)),
+            }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K, V> serde::Serialize for IMap<K, V>
+where
+    K: Eq + Hash + ImplicitClone + 'static + serde::Serialize,
+    V: PartialEq + ImplicitClone + 'static + serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut seq = serializer.serialize_map(Some(self.len()))?;
+        match self {
+            Self::Static(a) => {
+                for (k, v) in a.iter() {
+                    seq.serialize_entry(k, v)?;
                 }
             }
             Self::Rc(a) => {
@@ -382,10 +1195,66 @@ where
     }
 }
 
+/// Serialize and deserialize an [`IMap`] as a sequence of key-value pairs.
+///
+/// This module is meant to be used with `#[serde(with = "...")]` on an [`IMap`] field. Unlike the
+/// default map form, whose order is not guaranteed to be preserved by every format (e.g. JSON
+/// objects), the sequence form always preserves the order of the entries. If a key appears more
+/// than once, the last value is kept at the position of the first occurrence.
+///
+/// # Examples
+///
+/// ```
+/// # use implicit_clone::unsync::*;
+/// let mut de = serde_json::Deserializer::from_str(r#"[["foo", 1], ["bar", 2]]"#);
+/// let map: IMap<IString, u32> = imap_seq::deserialize(&mut de).unwrap();
+/// assert_eq!(map.keys().collect::<Vec<_>>(), ["foo", "bar"]);
+///
+/// let mut out = Vec::new();
+/// imap_seq::serialize(&map, &mut serde_json::Serializer::new(&mut out)).unwrap();
+/// assert_eq!(out, br#"[["foo",1],["bar",2]]"#);
+/// ```
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod imap_seq {
+    use std::hash::Hash;
+
+    use super::IMap;
+    use crate::ImplicitClone;
+
+    /// Serialize the map as a sequence of key-value pairs.
+    pub fn serialize<K, V, S>(map: &IMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Eq + Hash + ImplicitClone + 'static + serde::Serialize,
+        V: PartialEq + ImplicitClone + 'static + serde::Serialize,
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(map.iter())
+    }
+
+    /// Deserialize the map from a sequence of key-value pairs, preserving their order.
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<IMap<K, V>, D::Error>
+    where
+        K: Eq + Hash + ImplicitClone + 'static + serde::Deserialize<'de>,
+        V: PartialEq + ImplicitClone + 'static + serde::Deserialize<'de>,
+        D: serde::Deserializer<'de>,
+    {
+        <Vec<(K, V)> as serde::Deserialize>::deserialize(deserializer)
+            .map(|entries| entries.into_iter().collect())
+    }
+}
+
 #[cfg(test)]
 mod test_map {
     use super::*;
 
+    static_assertions::const_assert_eq!(
+        std::mem::size_of::<IMap<IString, IString>>(),
+        2 * std::mem::size_of::<usize>()
+    );
+
+    use crate::test_util::{self as counted, Counted, PanicOnEq};
+
     #[test]
     fn map_in_map() {
         let map_1 = [
@@ -428,6 +1297,12 @@ mod test_map {
         const _MAP: IMap<&str, u32> = IMap::Static(&[("foo", 1)]);
     }
 
+    #[test]
+    fn from_static() {
+        const MAP: IMap<&str, u32> = IMap::from_static(&[("foo", 1)]);
+        assert_eq!(MAP.get("foo"), Some(1));
+    }
+
     #[test]
     fn floats_in_map() {
         const _MAP_F32: IMap<u32, f32> = IMap::Static(&[]);
@@ -439,4 +1314,654 @@ mod test_map {
         let x: IMap<u32, u32> = IMap::Static(&[]);
         let _out = IMap::from(&x);
     }
+
+    #[test]
+    fn keys_values_nth_back() {
+        const STATIC: IMap<u32, u32> = IMap::Static(&[(1, 10), (2, 20), (3, 30), (4, 40)]);
+        let rc = STATIC.iter().collect::<IMap<u32, u32>>();
+
+        for map in [STATIC, rc] {
+            let mut stepped = map.keys();
+            stepped.next_back();
+            stepped.next_back();
+            assert_eq!(map.keys().nth_back(2), stepped.next_back());
+            assert_eq!(map.keys().nth_back(2), Some(2));
+
+            let mut stepped = map.values();
+            stepped.next_back();
+            stepped.next_back();
+            assert_eq!(map.values().nth_back(2), stepped.next_back());
+            assert_eq!(map.values().nth_back(2), Some(20));
+
+            let mut keys = map.keys();
+            assert_eq!(keys.len(), 4);
+            assert_eq!(keys.nth_back(1), Some(3));
+            assert_eq!(keys.len(), 2);
+            assert_eq!(keys.nth_back(5), None);
+            assert_eq!(keys.len(), 0);
+
+            assert_eq!(map.values().rev().collect::<Vec<_>>(), [40, 30, 20, 10]);
+        }
+    }
+
+    #[test]
+    fn into_builder_reuses_unique_storage() {
+        let map = IMap::<u32, u32>::from(Map::with_capacity(128));
+        let mut builder = map.into_builder();
+        let capacity = builder.capacity();
+        assert!(capacity >= 128);
+        for i in 0..100 {
+            builder.insert(i, i * 2);
+        }
+        assert_eq!(builder.capacity(), capacity);
+        let map = builder.build();
+        assert_eq!(map.len(), 100);
+        assert_eq!(map.get(&99), Some(198));
+    }
+
+    #[test]
+    fn into_builder_clones_shared_storage() {
+        let map = [(1, 10), (2, 20)].into_iter().collect::<IMap<u32, u32>>();
+        let other = map.clone();
+        let mut builder = map.into_builder();
+        builder.insert(3, 30);
+        let map = builder.build();
+        assert_eq!(map.len(), 3);
+        assert_eq!(other.len(), 2);
+
+        let map = IMap::<u32, u32>::Static(&[(1, 10)]);
+        let mut builder = map.into_builder();
+        builder.shift_remove(&1);
+        assert!(builder.build().is_empty());
+    }
+
+    #[test]
+    fn get_ref_does_not_clone() {
+        static STATIC: [(u32, Counted); 2] = [(1, Counted(10)), (2, Counted(20))];
+        let clones = counted::clones();
+        let rc = [(1, Counted(10)), (2, Counted(20))]
+            .into_iter()
+            .collect::<IMap<u32, Counted>>();
+
+        for map in [IMap::Static(&STATIC), rc] {
+            assert_eq!(map.get_index_ref(1), Some((&2, &Counted(20))));
+            assert_eq!(map.get_index_ref(2), None);
+            assert_eq!(map.get_full_ref(&1), Some((0, &1, &Counted(10))));
+            assert_eq!(map.get_full_ref(&3), None);
+        }
+        assert_eq!(counted::clones() - clones, 0);
+    }
+
+    #[test]
+    fn join() {
+        let a = [
+            (IString::from("foo"), 1),
+            (IString::from("bar"), 2),
+            (IString::from("baz"), 3),
+        ]
+        .into_iter()
+        .collect::<IMap<IString, u32>>();
+        let b = [(IString::from("qux"), 40), (IString::from("bar"), 20)]
+            .into_iter()
+            .collect::<IMap<IString, u32>>();
+        let joined = a.join(&b);
+        assert_eq!(
+            joined.iter().collect::<Vec<_>>(),
+            [
+                (IString::from("foo"), (Some(1), None)),
+                (IString::from("bar"), (Some(2), Some(20))),
+                (IString::from("baz"), (Some(3), None)),
+                (IString::from("qux"), (None, Some(40))),
+            ]
+        );
+
+        let empty = IMap::<IString, u32>::default();
+        assert_eq!(empty.join(&empty).len(), 0);
+        assert_eq!(empty.join(&b).get("qux"), Some((None, Some(40))));
+    }
+
+    #[test]
+    fn eq_index_map() {
+        let index_map = [(1, 10), (2, 20)].into_iter().collect::<Map<u32, u32>>();
+        assert_eq!(IMap::<u32, u32>::Static(&[(1, 10), (2, 20)]), index_map);
+        assert_eq!(IMap::from(index_map.clone()), index_map);
+        assert_ne!(IMap::<u32, u32>::Static(&[(2, 20), (1, 10)]), index_map);
+        assert_ne!(IMap::<u32, u32>::Static(&[(1, 10)]), index_map);
+        assert_ne!(IMap::<u32, u32>::Static(&[(1, 10), (2, 21)]), index_map);
+    }
+
+    #[test]
+    fn eq_hash_map() {
+        let hash_map = [(1, 10), (2, 20)]
+            .into_iter()
+            .collect::<std::collections::HashMap<u32, u32>>();
+        assert_eq!(IMap::<u32, u32>::Static(&[(1, 10), (2, 20)]), hash_map);
+        assert_eq!(IMap::<u32, u32>::Static(&[(2, 20), (1, 10)]), hash_map);
+        assert_eq!(
+            hash_map.clone().into_iter().collect::<IMap<_, _>>(),
+            hash_map
+        );
+        assert_ne!(IMap::<u32, u32>::Static(&[(1, 10)]), hash_map);
+        assert_ne!(IMap::<u32, u32>::Static(&[(1, 10), (3, 20)]), hash_map);
+    }
+
+    #[test]
+    fn eq_btree_map() {
+        let btree_map = [(1, 10), (2, 20)]
+            .into_iter()
+            .collect::<std::collections::BTreeMap<u32, u32>>();
+        assert_eq!(IMap::<u32, u32>::Static(&[(2, 20), (1, 10)]), btree_map);
+        assert_eq!(
+            btree_map.clone().into_iter().collect::<IMap<_, _>>(),
+            btree_map
+        );
+        assert_ne!(IMap::<u32, u32>::Static(&[(1, 10), (2, 21)]), btree_map);
+        assert_ne!(IMap::<u32, u32>::Static(&[]), btree_map);
+    }
+
+    #[test]
+    fn difference() {
+        let a = [
+            (IString::from("foo"), 1),
+            (IString::from("bar"), 2),
+            (IString::from("baz"), 3),
+        ]
+        .into_iter()
+        .collect::<IMap<IString, u32>>();
+        let b = [(IString::from("bar"), 20), (IString::from("qux"), 40)]
+            .into_iter()
+            .collect::<IMap<IString, u32>>();
+
+        assert_eq!(
+            a.difference(&b).collect::<Vec<_>>(),
+            [(IString::from("foo"), 1), (IString::from("baz"), 3)]
+        );
+        assert_eq!(
+            b.difference(&a).collect::<Vec<_>>(),
+            [(IString::from("qux"), 40)]
+        );
+        assert_eq!(
+            a.symmetric_difference(&b).collect::<Vec<_>>(),
+            [
+                (IString::from("foo"), 1),
+                (IString::from("baz"), 3),
+                (IString::from("qux"), 40),
+            ]
+        );
+        assert_eq!(a.difference(&a).count(), 0);
+        assert_eq!(a.symmetric_difference(&a).count(), 0);
+    }
+
+    #[test]
+    fn rename_key() {
+        let map = [("foo", 1), ("bar", 2), ("baz", 3)]
+            .into_iter()
+            .collect::<IMap<&str, u32>>();
+
+        let renamed = map.rename_key(&"bar", "qux");
+        assert_eq!(
+            renamed.iter().collect::<Vec<_>>(),
+            [("foo", 1), ("qux", 2), ("baz", 3)]
+        );
+
+        let renamed = map.rename_key(&"missing", "qux");
+        assert_eq!(renamed, map);
+
+        let renamed = map.rename_key(&"baz", "foo");
+        assert_eq!(renamed.iter().collect::<Vec<_>>(), [("bar", 2), ("foo", 3)]);
+
+        let map = IMap::<&str, u32>::Static(&[("foo", 1), ("bar", 2)]);
+        let renamed = map.rename_key(&"foo", "bar");
+        assert_eq!(renamed.iter().collect::<Vec<_>>(), [("bar", 1)]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn imap_seq_round_trip() {
+        let json = r#"[["zzz",1],["aaa",2],["mmm",3]]"#;
+        let mut de = serde_json::Deserializer::from_str(json);
+        let map: IMap<IString, u32> = imap_seq::deserialize(&mut de).unwrap();
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            [
+                (IString::from("zzz"), 1),
+                (IString::from("aaa"), 2),
+                (IString::from("mmm"), 3),
+            ]
+        );
+
+        let mut out = Vec::new();
+        imap_seq::serialize(&map, &mut serde_json::Serializer::new(&mut out)).unwrap();
+        assert_eq!(std::str::from_utf8(&out).unwrap(), json);
+
+        let static_map = IMap::<&str, u32>::Static(&[("b", 1), ("a", 2)]);
+        let mut out = Vec::new();
+        imap_seq::serialize(&static_map, &mut serde_json::Serializer::new(&mut out)).unwrap();
+        assert_eq!(std::str::from_utf8(&out).unwrap(), r#"[["b",1],["a",2]]"#);
+    }
+
+    #[test]
+    fn diff() {
+        let before = [
+            (IString::from("kept"), 1),
+            (IString::from("changed"), 2),
+            (IString::from("removed"), 3),
+        ]
+        .into_iter()
+        .collect::<IMap<IString, u32>>();
+        let after = [
+            (IString::from("added"), 4),
+            (IString::from("changed"), 5),
+            (IString::from("kept"), 1),
+        ]
+        .into_iter()
+        .collect::<IMap<IString, u32>>();
+
+        assert_eq!(
+            before.diff(&after),
+            [
+                MapChange::Added(IString::from("added"), 4),
+                MapChange::Changed(IString::from("changed"), 2, 5),
+                MapChange::Removed(IString::from("removed")),
+            ]
+        );
+        assert_eq!(
+            after.diff(&before),
+            [
+                MapChange::Changed(IString::from("changed"), 5, 2),
+                MapChange::Added(IString::from("removed"), 3),
+                MapChange::Removed(IString::from("added")),
+            ]
+        );
+        assert!(before.diff(&before).is_empty());
+        assert!(before
+            .diff(&IMap::default())
+            .iter()
+            .all(|change| matches!(change, MapChange::Removed(_))));
+    }
+
+    #[test]
+    fn filtered() {
+        let map = [
+            (IString::from("data-id"), 1),
+            (IString::from("class"), 20),
+            (IString::from("data-value"), 30),
+        ]
+        .into_iter()
+        .collect::<IMap<IString, u32>>();
+
+        let data = map.filtered_keys(|k| k.starts_with("data-"));
+        assert_eq!(data.keys().collect::<Vec<_>>(), ["data-id", "data-value"]);
+
+        let large = map.filtered_values(|v| *v >= 20);
+        assert_eq!(large.keys().collect::<Vec<_>>(), ["class", "data-value"]);
+
+        let both = map.filtered(|k, v| k.starts_with("data-") && *v >= 20);
+        assert_eq!(
+            both.iter().collect::<Vec<_>>(),
+            [(IString::from("data-value"), 30)]
+        );
+
+        match (&map, map.filtered_values(|_| true)) {
+            (IMap::Rc(a), IMap::Rc(b)) => assert!(Rc::ptr_eq(a, &b)),
+            _ => panic!("expected the map to be reused"),
+        }
+        assert!(matches!(map.filtered_keys(|_| false), IMap::Static(&[])));
+    }
+
+    #[test]
+    fn swap_indices() {
+        let map = [
+            (IString::from("a"), 1),
+            (IString::from("b"), 2),
+            (IString::from("c"), 3),
+        ]
+        .into_iter()
+        .collect::<IMap<IString, u32>>();
+        let swapped = map.swap_indices(0, 1);
+        assert_eq!(swapped.keys().collect::<Vec<_>>(), ["b", "a", "c"]);
+        assert_eq!(swapped.get("a"), Some(1));
+        assert_eq!(map.keys().collect::<Vec<_>>(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn swap_indices_out_of_bounds() {
+        IMap::<&str, u32>::Static(&[("a", 1)]).swap_indices(0, 1);
+    }
+
+    #[test]
+    fn move_index() {
+        let map = [
+            (IString::from("a"), 1),
+            (IString::from("b"), 2),
+            (IString::from("c"), 3),
+        ]
+        .into_iter()
+        .collect::<IMap<IString, u32>>();
+        let moved = map.move_index(2, 0);
+        assert_eq!(moved.keys().collect::<Vec<_>>(), ["c", "a", "b"]);
+        assert_eq!(moved.get("c"), Some(3));
+        assert_eq!(
+            map.move_index(0, 1).keys().collect::<Vec<_>>(),
+            ["b", "a", "c"]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn move_index_out_of_bounds() {
+        IMap::<&str, u32>::Static(&[("a", 1)]).move_index(1, 0);
+    }
+
+    #[test]
+    fn get_or_insert_with() {
+        let map = [(IString::from("foo"), 1)]
+            .into_iter()
+            .collect::<IMap<IString, u32>>();
+
+        let mut calls = 0;
+        let (map, value) = map.get_or_insert_with(IString::from("bar"), || {
+            calls += 1;
+            2
+        });
+        assert_eq!(value, 2);
+        assert_eq!(calls, 1);
+        assert_eq!(map.keys().collect::<Vec<_>>(), ["foo", "bar"]);
+
+        let (same, value) = map.get_or_insert_with(IString::from("foo"), || {
+            calls += 1;
+            3
+        });
+        assert_eq!(value, 1);
+        assert_eq!(calls, 1);
+        match (&map, same) {
+            (IMap::Rc(a), IMap::Rc(b)) => assert!(Rc::ptr_eq(a, &b)),
+            _ => panic!("expected the map to be reused"),
+        }
+    }
+
+    #[test]
+    fn collect_results() {
+        let map = [Ok((IString::from("foo"), 1)), Ok((IString::from("bar"), 2))]
+            .into_iter()
+            .collect::<Result<IMap<IString, u32>, &str>>()
+            .unwrap();
+        assert_eq!(map.get("foo"), Some(1));
+        assert_eq!(map.get("bar"), Some(2));
+
+        let mut seen = 0;
+        let result = [Ok((IString::from("foo"), 1)), Err("first"), Err("second")]
+            .into_iter()
+            .inspect(|_| seen += 1)
+            .collect::<Result<IMap<IString, u32>, &str>>();
+        assert_eq!(result, Err("first"));
+        assert_eq!(seen, 2);
+    }
+
+    #[test]
+    fn fold() {
+        let map = [
+            (IString::from("foo"), 1),
+            (IString::from("bar"), 2),
+            (IString::from("baz"), 39),
+        ]
+        .into_iter()
+        .collect::<IMap<IString, u32>>();
+        let summary = map.fold(String::new(), |mut acc, (k, v)| {
+            if !acc.is_empty() {
+                acc.push_str(", ");
+            }
+            acc.push_str(&format!("{k}={v}"));
+            acc
+        });
+        assert_eq!(summary, "foo=1, bar=2, baz=39");
+
+        let map = IMap::<&str, u32>::Static(&[("foo", 1), ("bar", 2)]);
+        assert_eq!(map.fold(0, |acc, (k, v)| acc + k.len() as u32 * v), 9);
+    }
+
+    #[test]
+    fn fold_and_sum_values() {
+        let map = [
+            (IString::from("foo"), 1),
+            (IString::from("bar"), 2),
+            (IString::from("baz"), 39),
+        ]
+        .into_iter()
+        .collect::<IMap<IString, u32>>();
+        assert_eq!(map.sum_values(), 42);
+        assert_eq!(
+            map.fold_values(Vec::new(), |mut acc, v| {
+                acc.push(*v);
+                acc
+            }),
+            [1, 2, 39]
+        );
+
+        let map = IMap::<IString, u32>::Static(&[]);
+        assert_eq!(map.sum_values(), 0);
+        assert_eq!(map.fold_values(7, |acc, v| acc + v), 7);
+    }
+
+    #[test]
+    fn eq_pointer_fast_path() {
+        static ENTRIES: [(u32, PanicOnEq); 2] = [(1, PanicOnEq), (2, PanicOnEq)];
+        let map = IMap::<u32, PanicOnEq>::Static(&ENTRIES);
+        assert_eq!(map, map.clone());
+
+        let map = [(1, PanicOnEq), (2, PanicOnEq)]
+            .into_iter()
+            .collect::<IMap<u32, PanicOnEq>>();
+        assert_eq!(map, map.clone());
+
+        // equal contents in distinct allocations
+        let a = [(1, 10), (2, 20)].into_iter().collect::<IMap<u32, u32>>();
+        let b = [(1, 10), (2, 20)].into_iter().collect::<IMap<u32, u32>>();
+        assert_eq!(a, b);
+        assert_ne!(
+            a,
+            [(1, 10), (2, 21)].into_iter().collect::<IMap<u32, u32>>()
+        );
+    }
+
+    #[test]
+    fn into_keys_and_values() {
+        let new_map = || {
+            [(Counted(1), Counted(10)), (Counted(2), Counted(20))]
+                .into_iter()
+                .collect::<IMap<Counted, Counted>>()
+        };
+
+        // unique storage: entries are moved out
+        let clones = counted::clones();
+        assert_eq!(
+            new_map().into_keys().collect::<Vec<_>>(),
+            [Counted(1), Counted(2)]
+        );
+        assert_eq!(
+            new_map().into_values().collect::<Vec<_>>(),
+            [Counted(10), Counted(20)]
+        );
+        assert_eq!(counted::clones() - clones, 0);
+
+        // shared storage: entries are cloned
+        let map = new_map();
+        let clones = counted::clones();
+        assert_eq!(
+            map.clone().into_keys().collect::<Vec<_>>(),
+            [Counted(1), Counted(2)]
+        );
+        assert_eq!(
+            map.clone().into_values().collect::<Vec<_>>(),
+            [Counted(10), Counted(20)]
+        );
+        assert_eq!(counted::clones() - clones, 4);
+
+        static STATIC: [(u32, u32); 2] = [(1, 10), (2, 20)];
+        assert_eq!(
+            IMap::Static(&STATIC).into_keys().collect::<Vec<_>>(),
+            [1, 2]
+        );
+        assert_eq!(
+            IMap::Static(&STATIC).into_values().collect::<Vec<_>>(),
+            [10, 20]
+        );
+    }
+
+    #[test]
+    fn capacity() {
+        assert_eq!(IMap::<u32, u32>::Static(&[(1, 1)]).capacity(), 0);
+        assert_eq!(IMap::<u32, u32>::default().capacity(), 0);
+
+        let map = IMap::<u32, u32>::from(Map::with_capacity(16));
+        assert!(map.capacity() >= 16);
+        assert!(map.is_empty());
+
+        let mut builder = map.into_builder();
+        builder.extend((0..100).map(|i| (i, i)));
+        let map = builder.build();
+        assert!(map.capacity() >= 100);
+
+        let mut builder = map.into_builder();
+        builder.truncate(2);
+        builder.shrink_to_fit();
+        let map = builder.build();
+        assert!(map.capacity() >= 2 && map.capacity() < 100);
+    }
+
+    #[test]
+    fn map_keys() {
+        let map = [(IString::from("foo"), 1), (IString::from("bar"), 2)]
+            .into_iter()
+            .collect::<IMap<IString, u32>>();
+        let prefixed = map.map_keys(|k| IString::from(format!("x-{}", k)));
+        assert_eq!(
+            prefixed.iter().collect::<Vec<_>>(),
+            [(IString::from("x-foo"), 1), (IString::from("x-bar"), 2)]
+        );
+    }
+
+    #[test]
+    fn map_keys_collision() {
+        let map = IMap::<u32, &str>::Static(&[(1, "a"), (2, "b"), (3, "c")]);
+        let parity = map.map_keys(|k| k % 2);
+        assert_eq!(parity.iter().collect::<Vec<_>>(), [(1, "c"), (0, "b")]);
+    }
+
+    #[test]
+    fn debug_is_consistent_across_variants() {
+        let static_map = IMap::<&str, u32>::Static(&[("foo", 1), ("bar", 2)]);
+        let rc_map = [("foo", 1), ("bar", 2)]
+            .into_iter()
+            .collect::<IMap<&str, u32>>();
+        for map in [static_map, rc_map] {
+            assert_eq!(format!("{:?}", map), r#"{"foo": 1, "bar": 2}"#);
+            assert_eq!(
+                format!("{:#?}", map),
+                "{\n    \"foo\": 1,\n    \"bar\": 2,\n}"
+            );
+        }
+        assert_eq!(format!("{:?}", IMap::<u32, u32>::default()), "{}");
+    }
+
+    #[test]
+    fn with_defaults() {
+        static DEFAULTS: [(&str, u32); 3] = [("a", 1), ("b", 2), ("c", 3)];
+        let map = [("b", 20), ("z", 26)]
+            .into_iter()
+            .collect::<IMap<&str, u32>>();
+        let merged = map.with_defaults(&DEFAULTS);
+        assert_eq!(
+            merged.iter().collect::<Vec<_>>(),
+            [("b", 20), ("z", 26), ("a", 1), ("c", 3)]
+        );
+
+        // nothing to add
+        let full = merged.with_defaults(&DEFAULTS);
+        assert!(matches!((&merged, &full), (IMap::Rc(a), IMap::Rc(b)) if Rc::ptr_eq(a, b)));
+
+        let empty = IMap::<&str, u32>::default().with_defaults(&DEFAULTS);
+        assert_eq!(empty.iter().collect::<Vec<_>>(), DEFAULTS);
+    }
+
+    #[test]
+    fn try_insert() {
+        let map = [(IString::from("foo"), 1)]
+            .into_iter()
+            .collect::<IMap<IString, u32>>();
+
+        let inserted = map.try_insert(IString::from("bar"), 2).unwrap();
+        assert_eq!(
+            inserted.iter().collect::<Vec<_>>(),
+            [(IString::from("foo"), 1), (IString::from("bar"), 2)]
+        );
+        assert_eq!(map.len(), 1);
+
+        assert_eq!(inserted.try_insert(IString::from("foo"), 10), Err(1));
+        assert_eq!(inserted.get("foo"), Some(1));
+    }
+
+    #[test]
+    fn shrink_to_fit() {
+        let mut builder = IMap::<u32, u32>::default().into_builder();
+        builder.extend((0..100).map(|i| (i, i)));
+        builder.truncate(10);
+        let mut map = builder.build();
+        assert!(map.capacity() > map.len());
+
+        let shared = map.clone();
+        map.shrink_to_fit();
+        assert!(map.capacity() > map.len());
+        drop(shared);
+
+        map.shrink_to_fit();
+        assert_eq!(map.capacity(), map.len());
+        assert_eq!(map.len(), 10);
+
+        let mut map = IMap::<u32, u32>::Static(&[(1, 1)]);
+        map.shrink_to_fit();
+        assert_eq!(map.capacity(), 0);
+    }
+
+    #[test]
+    fn iter_sorted() {
+        let map = [
+            (IString::from("zzz"), 1),
+            (IString::from("aaa"), 2),
+            (IString::from("mmm"), 3),
+        ]
+        .into_iter()
+        .collect::<IMap<IString, u32>>();
+        assert_eq!(
+            map.iter_sorted().collect::<Vec<_>>(),
+            [
+                (IString::from("aaa"), 2),
+                (IString::from("mmm"), 3),
+                (IString::from("zzz"), 1),
+            ]
+        );
+        assert_eq!(
+            map.keys().collect::<Vec<_>>(),
+            [
+                IString::from("zzz"),
+                IString::from("aaa"),
+                IString::from("mmm")
+            ]
+        );
+        assert_eq!(IMap::<u32, u32>::default().iter_sorted().count(), 0);
+    }
+
+    #[test]
+    fn get_many() {
+        let map =
+            IMap::<IString, u32>::from_iter([(IString::from("foo"), 1), (IString::from("bar"), 2)]);
+        assert_eq!(
+            map.get_many(["foo", "baz", "bar"]),
+            [Some(1), None, Some(2)]
+        );
+
+        const STATIC: IMap<&str, u32> = IMap::Static(&[("foo", 1), ("bar", 2)]);
+        assert_eq!(STATIC.get_many(["bar", "qux"]), [Some(2), None]);
+    }
 }
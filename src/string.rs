@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::fmt::{self, Debug};
+use std::ops::RangeBounds;
 use std::str::FromStr;
 
 use crate::ImplicitClone;
@@ -11,6 +12,9 @@ use super::Rc;
 ///
 /// This type is cheap to clone and thus implements [`ImplicitClone`]. It can be created based on a
 /// `&'static str` or based on a reference counted string slice ([`str`]).
+///
+/// An `IString` is three words large (the size of a `&str` plus a tag), like a [`String`]. This
+/// is checked by the tests of this crate.
 #[derive(Debug, Clone)]
 pub enum IString {
     /// A static string slice.
@@ -19,39 +23,817 @@ pub enum IString {
     Rc(Rc<str>),
 }
 
-impl IString {
-    /// Extracts a string slice containing the entire `IString`.
+impl IString {
+    /// Creates an `IString` from a static string slice without allocation.
+    ///
+    /// This is usable in `const` contexts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// const GREETING: IString = IString::from_static("hello");
+    ///
+    /// assert_eq!(GREETING, "hello");
+    /// ```
+    #[inline]
+    pub const fn from_static(s: &'static str) -> IString {
+        IString::Static(s)
+    }
+
+    /// Creates an `IString` by copying a string slice that is not `'static` into a new reference
+    /// counted string.
+    ///
+    /// This always allocates, even if `s` happens to be `'static`. Use
+    /// [`from_static`](Self::from_static) or `IString::from` to store a `'static` string slice
+    /// without allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let owned = String::from("hello");
+    /// let s = IString::from_str_owned(&owned);
+    ///
+    /// assert_eq!(s, "hello");
+    /// assert!(matches!(s, IString::Rc(_)));
+    /// ```
+    #[inline]
+    pub fn from_str_owned(s: &str) -> IString {
+        IString::Rc(Rc::from(s))
+    }
+
+    /// Extracts a string slice containing the entire `IString`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from("foo");
+    ///
+    /// assert_eq!("foo", s.as_str());
+    /// ```
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Static(s) => s,
+            Self::Rc(s) => s,
+        }
+    }
+
+    /// Obtain the contents of [`IString`] as a [`Cow`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// use std::borrow::Cow;
+    /// let s = IString::from("foo");
+    ///
+    /// let cow: Cow<'_, str> = s.as_cow();
+    /// ```
+    pub fn as_cow(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.as_str())
+    }
+
+    /// Consumes the `IString` and returns a `'static` string slice.
+    ///
+    /// A `Static` string returns its string slice without cost. For an `Rc` string, the reference
+    /// count is intentionally leaked so the allocation is never freed, like
+    /// [`Box::leak`]. This is meant for values that live until the end of the program, such as
+    /// long-lived caches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from(format!("{}-{}", "foo", 42));
+    /// let leaked: &'static str = s.leak();
+    ///
+    /// assert_eq!(leaked, "foo-42");
+    /// ```
+    pub fn leak(self) -> &'static str {
+        match self {
+            Self::Static(s) => s,
+            // SAFETY: the strong count consumed by `into_raw` is never released, so the
+            // allocation outlives every borrow. The string data is immutable and the reference
+            // does not give access to the reference counts.
+            Self::Rc(s) => unsafe { &*Rc::into_raw(s) },
+        }
+    }
+
+    /// Converts this `IString` into a `String` if it is the only reference to its content.
+    ///
+    /// Returns `Err(self)` for a `Static` string or an `Rc` string that is shared. A reference
+    /// counted string stores its counts next to the content, so its allocation cannot be taken
+    /// over by a `String`: the content is copied once and the `Rc` is freed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from(String::from("foo"));
+    /// assert_eq!(s.try_into_string(), Ok(String::from("foo")));
+    ///
+    /// let s = IString::from(String::from("foo"));
+    /// let other = s.clone();
+    /// assert_eq!(s.try_into_string(), Err(other));
+    /// ```
+    pub fn try_into_string(self) -> Result<String, IString> {
+        match self {
+            Self::Rc(s) if Rc::strong_count(&s) == 1 && Rc::weak_count(&s) == 0 => {
+                Ok(String::from(&*s))
+            }
+            s => Err(s),
+        }
+    }
+
+    /// Returns the number of strong references to the backing `Rc`, or 1 if this string is
+    /// `Static` since it is never shared.
+    ///
+    /// This is meant for debugging, for example to check that a string is shared rather than
+    /// copied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from(String::from("foo"));
+    /// let other = s.clone();
+    /// assert_eq!(s.strong_count(), 2);
+    /// ```
+    #[inline]
+    pub fn strong_count(&self) -> usize {
+        match self {
+            Self::Static(_) => 1,
+            Self::Rc(s) => Rc::strong_count(s),
+        }
+    }
+
+    /// Returns `true` if both strings point to the same content: the same `Static` string slice or
+    /// the same `Rc` allocation.
+    ///
+    /// Strings that are equal but stored separately are not pointer-equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from(String::from("foo"));
+    ///
+    /// assert!(s.ptr_eq(&s.clone()));
+    /// assert!(!s.ptr_eq(&IString::from(String::from("foo"))));
+    /// ```
+    #[inline]
+    pub fn ptr_eq(&self, other: &IString) -> bool {
+        match (self, other) {
+            (Self::Static(a), Self::Static(b)) => std::ptr::eq(*a, *b),
+            (Self::Rc(a), Self::Rc(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    /// Compares an optional `IString` with an optional string slice.
+    ///
+    /// Trait implementations comparing `Option<IString>` and `Option<&str>` are not possible, so
+    /// this is provided as an associated function instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = Some(IString::from("foo"));
+    ///
+    /// assert!(IString::eq_opt(&s, Some("foo")));
+    /// assert!(!IString::eq_opt(&s, None));
+    /// assert!(IString::eq_opt(&None, None));
+    /// ```
+    #[inline]
+    pub fn eq_opt(this: &Option<IString>, other: Option<&str>) -> bool {
+        this.as_ref().map(IString::as_str) == other
+    }
+
+    /// Returns an equal string where empty content is always `Static("")`.
+    ///
+    /// A non-empty string is returned as-is, keeping its variant. This never allocates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from(String::new());
+    ///
+    /// assert!(s.as_rc().is_some());
+    /// assert_eq!(s.normalized().as_static(), Some(""));
+    /// ```
+    pub fn normalized(&self) -> IString {
+        if self.is_empty() {
+            Self::Static("")
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Returns the static string slice if this string is `Static`, or `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// assert_eq!(IString::from("foo").as_static(), Some("foo"));
+    /// assert_eq!(IString::from(String::from("foo")).as_static(), None);
+    /// ```
+    #[inline]
+    pub fn as_static(&self) -> Option<&'static str> {
+        match self {
+            Self::Static(s) => Some(s),
+            Self::Rc(_) => None,
+        }
+    }
+
+    /// Returns a reference to the backing `Rc` if this string is `Rc`, or `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from(String::from("foo"));
+    /// assert_eq!(s.as_rc().map(|rc| &**rc), Some("foo"));
+    /// assert_eq!(IString::from("foo").as_rc(), None);
+    /// ```
+    #[inline]
+    pub fn as_rc(&self) -> Option<&Rc<str>> {
+        match self {
+            Self::Static(_) => None,
+            Self::Rc(s) => Some(s),
+        }
+    }
+
+    /// Returns the length of this `IString` in bytes.
+    ///
+    /// This is the same as `len()` obtained through [`Deref`](std::ops::Deref) but the name makes
+    /// it explicit that the result is not a number of characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from("héllo");
+    ///
+    /// assert_eq!(s.len_bytes(), 6);
+    /// ```
+    #[inline]
+    pub fn len_bytes(&self) -> usize {
+        self.as_str().len()
+    }
+
+    /// Returns the number of [`char`]s in this `IString`.
+    ///
+    /// Computes in **O(n)** time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from("héllo");
+    ///
+    /// assert_eq!(s.len_chars(), 5);
+    /// ```
+    #[inline]
+    pub fn len_chars(&self) -> usize {
+        self.as_str().chars().count()
+    }
+
+    /// Checks that the `index`-th byte is the first byte in a UTF-8 code point sequence or the end
+    /// of the string.
+    ///
+    /// The start and end of the string (when `index == self.len_bytes()`) are considered to be
+    /// boundaries.
+    ///
+    /// Returns `false` if `index` is greater than `self.len_bytes()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from("héllo");
+    ///
+    /// assert!(s.is_char_boundary(1));
+    /// // byte 2 lies inside `é`
+    /// assert!(!s.is_char_boundary(2));
+    /// assert!(s.is_char_boundary(3));
+    /// ```
+    #[inline]
+    pub fn is_char_boundary(&self, index: usize) -> bool {
+        self.as_str().is_char_boundary(index)
+    }
+
+    /// Returns an iterator over the bytes of this `IString`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from("hé");
+    ///
+    /// assert_eq!(s.bytes().collect::<Vec<_>>(), [b'h', 0xc3, 0xa9]);
+    /// ```
+    #[inline]
+    pub fn bytes(&self) -> std::str::Bytes<'_> {
+        self.as_str().bytes()
+    }
+
+    /// Returns the UTF-8 bytes of this `IString` as an [`IArray`](super::IArray).
+    ///
+    /// If this string is `Static`, the result is a `Static` array over the same bytes and nothing
+    /// is allocated. Otherwise, the bytes are copied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::*;
+    /// let s = IString::from("foo");
+    ///
+    /// assert_eq!(s.as_byte_array(), b"foo");
+    /// assert!(matches!(s.as_byte_array(), IArray::Static(b"foo")));
+    /// ```
+    pub fn as_byte_array(&self) -> super::IArray<u8> {
+        match self {
+            Self::Static(s) => super::IArray::Static(s.as_bytes()),
+            Self::Rc(s) => super::IArray::from_slice_owned(s.as_bytes()),
+        }
+    }
+
+    /// Checks if all characters of this `IString` are within the ASCII range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// assert!(IString::from("foo_bar").is_ascii());
+    /// assert!(!IString::from("héllo").is_ascii());
+    /// ```
+    #[inline]
+    pub fn is_ascii(&self) -> bool {
+        self.as_str().is_ascii()
+    }
+
+    /// Converts this `IString` to its ASCII upper case equivalent in-place.
+    ///
+    /// ASCII letters 'a' to 'z' are mapped to 'A' to 'Z', but non-ASCII letters are unchanged.
+    /// Since the length does not change, a reference counted string that is not shared is modified
+    /// in-place. Otherwise, the string is copied into a new reference counted string, unless it is
+    /// already upper case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let mut s = IString::from("héllo");
+    /// s.make_ascii_uppercase();
+    ///
+    /// assert_eq!(s, "HéLLO");
+    /// ```
+    pub fn make_ascii_uppercase(&mut self) {
+        if let Self::Rc(rc) = self {
+            if let Some(s) = Rc::get_mut(rc) {
+                s.make_ascii_uppercase();
+                return;
+            }
+        }
+        if self.bytes().any(|b| b.is_ascii_lowercase()) {
+            *self = IString::from(self.as_str().to_ascii_uppercase());
+        }
+    }
+
+    /// Returns the byte index of the first occurrence of the string slice `pat`, or `None` if it
+    /// is not found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from("héllo héllo");
+    ///
+    /// assert_eq!(s.find_str("llo"), Some(3));
+    /// assert_eq!(s.find_str("xyz"), None);
+    /// ```
+    #[inline]
+    pub fn find_str(&self, pat: &str) -> Option<usize> {
+        self.as_str().find(pat)
+    }
+
+    /// Returns the byte index of the last occurrence of the string slice `pat`, or `None` if it
+    /// is not found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from("héllo héllo");
+    ///
+    /// assert_eq!(s.rfind_str("llo"), Some(10));
+    /// assert_eq!(s.rfind_str("xyz"), None);
+    /// ```
+    #[inline]
+    pub fn rfind_str(&self, pat: &str) -> Option<usize> {
+        self.as_str().rfind(pat)
+    }
+
+    /// Returns the number of non-overlapping occurrences of `pat`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from("abcXXabcYYabc");
+    ///
+    /// assert_eq!(s.match_count("abc"), 3);
+    /// assert_eq!(s.match_count("xyz"), 0);
+    /// ```
+    #[inline]
+    pub fn match_count(&self, pat: &str) -> usize {
+        self.as_str().matches(pat).count()
+    }
+
+    /// Returns the byte indices of the non-overlapping occurrences of `pat`.
+    ///
+    /// This forwards to [`str::match_indices`]. When matches overlap, only the first one is
+    /// counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from("abcXXabcYYabc");
+    ///
+    /// assert_eq!(s.match_offsets("abc"), [0, 5, 10]);
+    /// ```
+    pub fn match_offsets(&self, pat: &str) -> super::IArray<usize> {
+        super::IArray::from_vec_exact(self.as_str().match_indices(pat).map(|(i, _)| i).collect())
+    }
+
+    /// Splits the string on the first occurrence of `delim`.
+    ///
+    /// Returns `None` if `delim` is not found. If this string is `Static`, both halves are
+    /// `Static` sub-slices, otherwise each half is copied into a new reference counted string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from("key=value=other");
+    ///
+    /// assert_eq!(s.split_once_istring('='), Some(("key".into(), "value=other".into())));
+    /// assert_eq!(s.split_once_istring(':'), None);
+    /// ```
+    pub fn split_once_istring(&self, delim: char) -> Option<(IString, IString)> {
+        match self {
+            Self::Static(s) => s
+                .split_once(delim)
+                .map(|(a, b)| (Self::Static(a), Self::Static(b))),
+            Self::Rc(s) => s
+                .split_once(delim)
+                .map(|(a, b)| (Self::Rc(Rc::from(a)), Self::Rc(Rc::from(b)))),
+        }
+    }
+
+    /// Splits the string on the last occurrence of `delim`.
+    ///
+    /// Returns `None` if `delim` is not found. If this string is `Static`, both halves are
+    /// `Static` sub-slices, otherwise each half is copied into a new reference counted string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from("key=value=other");
+    ///
+    /// assert_eq!(s.rsplit_once_istring('='), Some(("key=value".into(), "other".into())));
+    /// assert_eq!(s.rsplit_once_istring(':'), None);
+    /// ```
+    pub fn rsplit_once_istring(&self, delim: char) -> Option<(IString, IString)> {
+        match self {
+            Self::Static(s) => s
+                .rsplit_once(delim)
+                .map(|(a, b)| (Self::Static(a), Self::Static(b))),
+            Self::Rc(s) => s
+                .rsplit_once(delim)
+                .map(|(a, b)| (Self::Rc(Rc::from(a)), Self::Rc(Rc::from(b)))),
+        }
+    }
+
+    /// Splits the string in two at the byte index `mid`.
+    ///
+    /// If this string is `Static`, both halves are `Static` sub-slices, otherwise each half is
+    /// copied into a new reference counted string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid` is out of bounds or does not lie on a [`char`] boundary, like
+    /// [`str::split_at`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from("héllo");
+    ///
+    /// assert_eq!(s.split_at_istring(3), ("hé".into(), "llo".into()));
+    /// ```
+    pub fn split_at_istring(&self, mid: usize) -> (IString, IString) {
+        match self {
+            Self::Static(s) => {
+                let (a, b) = s.split_at(mid);
+                (Self::Static(a), Self::Static(b))
+            }
+            Self::Rc(s) => {
+                let (a, b) = s.split_at(mid);
+                (Self::Rc(Rc::from(a)), Self::Rc(Rc::from(b)))
+            }
+        }
+    }
+
+    /// Splits the string on Unicode whitespace, dropping empty tokens.
+    ///
+    /// This behaves like [`str::split_whitespace`]. If this string is `Static`, the tokens are
+    /// `Static` sub-slices, otherwise each token is copied into a new reference counted string.
+    /// An empty or all-whitespace string yields [`IArray::EMPTY`](super::IArray::EMPTY).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from(String::from("  foo  bar\tbaz\n"));
+    ///
+    /// assert_eq!(s.split_whitespace_istrings(), ["foo", "bar", "baz"]);
+    /// ```
+    pub fn split_whitespace_istrings(&self) -> super::IArray<IString> {
+        let tokens = match self {
+            Self::Static(s) => s.split_whitespace().map(Self::Static).collect(),
+            Self::Rc(s) => s
+                .split_whitespace()
+                .map(|token| Self::Rc(Rc::from(token)))
+                .collect(),
+        };
+        super::IArray::from_vec_exact(tokens)
+    }
+
+    /// Pads the start of the string with `fill` so it is at least `width` characters long.
+    ///
+    /// The width is measured in [`char`]s. If the string is already wide enough, it is returned
+    /// as is without allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from("42");
+    ///
+    /// assert_eq!(s.pad_start(5, '0'), "00042");
+    /// assert_eq!(s.pad_start(1, '0'), "42");
+    /// ```
+    pub fn pad_start(&self, width: usize, fill: char) -> IString {
+        let len = self.len_chars();
+        if len >= width {
+            return self.clone();
+        }
+        let mut buf = String::with_capacity(self.len() + (width - len) * fill.len_utf8());
+        buf.extend(std::iter::repeat(fill).take(width - len));
+        buf.push_str(self);
+        IString::from(buf)
+    }
+
+    /// Pads the end of the string with `fill` so it is at least `width` characters long.
+    ///
+    /// The width is measured in [`char`]s. If the string is already wide enough, it is returned
+    /// as is without allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from("foo");
+    ///
+    /// assert_eq!(s.pad_end(5, '.'), "foo..");
+    /// assert_eq!(s.pad_end(3, '.'), "foo");
+    /// ```
+    pub fn pad_end(&self, width: usize, fill: char) -> IString {
+        let len = self.len_chars();
+        if len >= width {
+            return self.clone();
+        }
+        let mut buf = String::with_capacity(self.len() + (width - len) * fill.len_utf8());
+        buf.push_str(self);
+        buf.extend(std::iter::repeat(fill).take(width - len));
+        IString::from(buf)
+    }
+
+    /// Returns a copy of the string with its first character in uppercase, the rest unchanged.
+    ///
+    /// The string is returned as-is when its first character is already uppercase (or has no
+    /// uppercase form), otherwise a new reference counted string is created.
+    ///
+    /// The first character is converted with [`char::to_uppercase`], which is correct for ASCII
+    /// but may expand to several characters for some Unicode characters (e.g. `'ß'` becomes
+    /// `"SS"`). Title case digraphs like `'ǆ'` are converted to their uppercase form (`'Ǆ'`), not
+    /// their title case form (`'ǅ'`).
     ///
     /// # Examples
     ///
-    /// Basic usage:
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// assert_eq!(IString::from("hello world").capitalize(), "Hello world");
+    /// assert_eq!(IString::from("Hello").capitalize(), "Hello");
+    /// assert_eq!(IString::from("").capitalize(), "");
+    /// ```
+    pub fn capitalize(&self) -> IString {
+        let mut chars = self.chars();
+        let first = match chars.next() {
+            Some(first) => first,
+            None => return self.clone(),
+        };
+        let mut upper = first.to_uppercase();
+        if upper.len() == 1 && upper.next() == Some(first) {
+            return self.clone();
+        }
+        let mut buf = String::with_capacity(self.len());
+        buf.extend(first.to_uppercase());
+        buf.push_str(chars.as_str());
+        IString::from(buf)
+    }
+
+    /// Returns a string with all prefixes and suffixes matching `pat` removed.
+    ///
+    /// If this string is `Static`, the result is a `Static` sub-slice. Otherwise, the string is
+    /// returned as-is when nothing is trimmed, or copied into a new reference counted string.
+    ///
+    /// # Examples
     ///
     /// ```
     /// # use implicit_clone::unsync::IString;
-    /// let s = IString::from("foo");
+    /// let s = IString::from("\"quoted\"");
     ///
-    /// assert_eq!("foo", s.as_str());
+    /// assert_eq!(s.trim_matches_istring('"'), "quoted");
+    /// assert_eq!(s.trim_matches_istring('\''), s);
     /// ```
-    pub fn as_str(&self) -> &str {
-        match self {
-            Self::Static(s) => s,
-            Self::Rc(s) => s,
-        }
+    pub fn trim_matches_istring(&self, pat: char) -> IString {
+        self.map_substr(|s| s.trim_matches(pat))
     }
 
-    /// Obtain the contents of [`IString`] as a [`Cow`].
+    /// Returns a string with all prefixes matching `pat` removed.
+    ///
+    /// See [`trim_matches_istring`](Self::trim_matches_istring) for details about allocation.
     ///
     /// # Examples
     ///
     /// ```
     /// # use implicit_clone::unsync::IString;
-    /// use std::borrow::Cow;
-    /// let s = IString::from("foo");
+    /// let s = IString::from("[[foo]]");
     ///
-    /// let cow: Cow<'_, str> = s.as_cow();
+    /// assert_eq!(s.trim_start_matches_istring('['), "foo]]");
     /// ```
-    pub fn as_cow(&self) -> Cow<'_, str> {
-        Cow::Borrowed(self.as_str())
+    pub fn trim_start_matches_istring(&self, pat: char) -> IString {
+        self.map_substr(|s| s.trim_start_matches(pat))
+    }
+
+    /// Returns a string with all suffixes matching `pat` removed.
+    ///
+    /// See [`trim_matches_istring`](Self::trim_matches_istring) for details about allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from("[[foo]]");
+    ///
+    /// assert_eq!(s.trim_end_matches_istring(']'), "[[foo");
+    /// ```
+    pub fn trim_end_matches_istring(&self, pat: char) -> IString {
+        self.map_substr(|s| s.trim_end_matches(pat))
+    }
+
+    /// Returns a string keeping at most `max_chars` characters.
+    ///
+    /// Characters are counted as Unicode scalar values, so a character is never split. See
+    /// [`trim_matches_istring`](Self::trim_matches_istring) for details about allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from("héllo");
+    ///
+    /// assert_eq!(s.truncate_chars(2), "hé");
+    /// assert_eq!(s.truncate_chars(10), s);
+    /// ```
+    pub fn truncate_chars(&self, max_chars: usize) -> IString {
+        self.map_substr(|s| match s.char_indices().nth(max_chars) {
+            Some((i, _)) => &s[..i],
+            None => s,
+        })
+    }
+
+    /// Returns a new string with the given byte range replaced by `replacement`.
+    ///
+    /// The result is always a new reference counted string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start or end of the range is out of bounds or does not lie on a [`char`]
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::unsync::IString;
+    /// let s = IString::from("hello world");
+    ///
+    /// assert_eq!(s.replace_range(6.., "there"), "hello there");
+    /// assert_eq!(s.replace_range(5..5, ","), "hello, world");
+    /// ```
+    pub fn replace_range<R: RangeBounds<usize>>(&self, range: R, replacement: &str) -> IString {
+        let mut buf = String::from(self.as_str());
+        buf.replace_range(range, replacement);
+        IString::from(buf)
+    }
+
+    fn map_substr(&self, f: impl FnOnce(&str) -> &str) -> IString {
+        match self {
+            Self::Static(s) => Self::Static(f(s)),
+            Self::Rc(s) => {
+                let sub = f(s);
+                if sub.len() == s.len() {
+                    self.clone()
+                } else {
+                    Self::Rc(Rc::from(sub))
+                }
+            }
+        }
+    }
+}
+
+/// A builder to accumulate formatted text into an [`IString`].
+///
+/// It implements [`fmt::Write`] so it can be used with [`write!`] and converted once into an
+/// [`IString`] holding a single reference counted string slice.
+///
+/// # Examples
+///
+/// ```
+/// # use implicit_clone::unsync::{IString, IStringBuilder};
+/// use std::fmt::Write;
+/// let mut builder = IStringBuilder::new();
+/// write!(builder, "{}-{}", "foo", 42).unwrap();
+/// builder.push_str("!");
+///
+/// let s: IString = builder.finish();
+/// assert_eq!(s, "foo-42!");
+/// ```
+#[derive(Debug, Default)]
+pub struct IStringBuilder {
+    buf: String,
+}
+
+impl IStringBuilder {
+    /// Creates a new empty `IStringBuilder`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new empty `IStringBuilder` with at least the specified capacity in bytes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: String::with_capacity(capacity),
+        }
+    }
+
+    /// Appends a string slice to the end of the builder.
+    pub fn push_str(&mut self, s: &str) {
+        self.buf.push_str(s);
+    }
+
+    /// Appends a [`char`] to the end of the builder.
+    pub fn push(&mut self, c: char) {
+        self.buf.push(c);
+    }
+
+    /// Consumes the builder and returns the accumulated [`IString`].
+    pub fn finish(self) -> IString {
+        IString::Rc(Rc::from(self.buf))
+    }
+}
+
+impl fmt::Write for IStringBuilder {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.buf.push_str(s);
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.buf.push(c);
+        Ok(())
     }
 }
 
@@ -106,6 +888,36 @@ impl From<std::fmt::Arguments<'_>> for IString {
     }
 }
 
+impl<'a> FromIterator<&'a str> for IString {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(it: I) -> Self {
+        let s = it.into_iter().collect::<String>();
+        if s.is_empty() {
+            Self::default()
+        } else {
+            Self::from(s)
+        }
+    }
+}
+
+impl FromIterator<IString> for IString {
+    fn from_iter<I: IntoIterator<Item = IString>>(it: I) -> Self {
+        let mut it = it.into_iter();
+        let first = match it.next() {
+            Some(first) => first,
+            None => return Self::default(),
+        };
+        let second = match it.next() {
+            Some(second) => second,
+            None => return first,
+        };
+        let mut buf = String::with_capacity(first.len() + second.len());
+        buf.push_str(&first);
+        buf.push_str(&second);
+        it.for_each(|s| buf.push_str(&s));
+        Self::from(buf)
+    }
+}
+
 impl From<&IString> for IString {
     fn from(s: &IString) -> IString {
         s.clone()
@@ -130,7 +942,13 @@ macro_rules! impl_cmp_as_str {
 
 impl Eq for IString {}
 
-impl_cmp_as_str!(PartialEq::<IString, IString>);
+impl PartialEq for IString {
+    fn eq(&self, other: &Self) -> bool {
+        // Clones and interned strings share their pointer so the bytes don't need to be compared.
+        self.ptr_eq(other) || PartialEq::eq(self.as_str(), other.as_str())
+    }
+}
+
 impl_cmp_as_str!(PartialEq::<IString, str>);
 impl_cmp_as_str!(PartialEq::<str, IString>);
 impl_cmp_as_str!(PartialEq::<IString, &str>);
@@ -213,8 +1031,14 @@ impl<'de> serde::Deserialize<'de> for IString {
 
 #[cfg(test)]
 mod test_string {
+    use super::super::IArray;
     use super::*;
 
+    static_assertions::const_assert_eq!(
+        std::mem::size_of::<IString>(),
+        3 * std::mem::size_of::<usize>()
+    );
+
     //
     // Frames wrap a value with a particular syntax
     // that may not be easy to write with plain macro_rules arg types
@@ -376,6 +1200,7 @@ mod test_string {
     #[test]
     fn const_string() {
         const _STRING: IString = IString::Static("foo");
+        const _FROM_STATIC: IString = IString::from_static("x");
     }
 
     #[test]
@@ -427,4 +1252,507 @@ mod test_string {
         assert!(matches!(s, IString::Rc(_)));
         assert_eq!(s, "Hello Jane!");
     }
+
+    #[test]
+    fn len_bytes_and_chars() {
+        for s in [IString::Static("héllo"), IString::Rc(Rc::from("héllo"))] {
+            assert_eq!(s.len_bytes(), 6);
+            assert_eq!(s.len_chars(), 5);
+            assert_eq!(s.len_bytes(), s.len());
+        }
+    }
+
+    #[test]
+    fn is_char_boundary() {
+        let s = IString::from("héllo");
+        assert!(s.is_char_boundary(0));
+        assert!(s.is_char_boundary(1));
+        assert!(!s.is_char_boundary(2));
+        assert!(s.is_char_boundary(3));
+        assert!(s.is_char_boundary(6));
+        assert!(!s.is_char_boundary(7));
+    }
+
+    #[test]
+    fn builder() {
+        use std::fmt::Write;
+
+        let mut builder = IStringBuilder::with_capacity(16);
+        for i in 0..3 {
+            write!(builder, "{i},").unwrap();
+        }
+        builder.push('x');
+        let s = builder.finish();
+        assert!(matches!(s, IString::Rc(_)));
+        assert_eq!(s, "0,1,2,x");
+
+        assert_eq!(IStringBuilder::new().finish(), "");
+    }
+
+    #[test]
+    fn pad() {
+        let s = IString::Static("héllo");
+        assert_eq!(s.pad_start(7, ' '), "  héllo");
+        assert_eq!(s.pad_end(7, ' '), "héllo  ");
+        assert!(matches!(s.pad_start(7, ' '), IString::Rc(_)));
+
+        assert!(matches!(s.pad_start(5, ' '), IString::Static("héllo")));
+        assert!(matches!(s.pad_end(2, ' '), IString::Static("héllo")));
+
+        assert_eq!(s.pad_start(7, 'é'), "ééhéllo");
+        assert_eq!(s.pad_end(6, '→'), "héllo→");
+
+        let rc = Rc::<str>::from("foo");
+        let s = IString::Rc(Rc::clone(&rc));
+        assert!(matches!(s.pad_end(3, ' '), IString::Rc(ref s) if Rc::ptr_eq(s, &rc)));
+    }
+
+    #[test]
+    fn split_once_istring() {
+        let s = IString::Static("a=b=c");
+        assert!(matches!(
+            s.split_once_istring('='),
+            Some((IString::Static("a"), IString::Static("b=c")))
+        ));
+        assert!(matches!(
+            s.rsplit_once_istring('='),
+            Some((IString::Static("a=b"), IString::Static("c")))
+        ));
+        assert!(s.split_once_istring(':').is_none());
+        assert!(s.rsplit_once_istring(':').is_none());
+
+        let s = IString::Rc(Rc::from("a=b=c"));
+        let (a, b) = s.split_once_istring('=').unwrap();
+        assert!(matches!(a, IString::Rc(_)));
+        assert_eq!((a, b), ("a".into(), "b=c".into()));
+        let (a, b) = s.rsplit_once_istring('=').unwrap();
+        assert!(matches!(b, IString::Rc(_)));
+        assert_eq!((a, b), ("a=b".into(), "c".into()));
+        assert!(s.split_once_istring(':').is_none());
+        assert!(s.rsplit_once_istring(':').is_none());
+
+        // `str::split_once` is still reachable through `Deref` with any pattern.
+        let halves: Option<(&str, &str)> = s.split_once("=");
+        assert_eq!(halves, Some(("a", "b=c")));
+    }
+
+    #[test]
+    fn from_iter_str() {
+        let s = ["a", "b", "c"].into_iter().collect::<IString>();
+        assert!(matches!(s, IString::Rc(_)));
+        assert_eq!(s, "abc");
+
+        let s = std::iter::empty::<&str>().collect::<IString>();
+        assert!(matches!(s, IString::Static("")));
+    }
+
+    #[test]
+    fn from_iter_istring() {
+        let s = ["a", "b", "c"]
+            .into_iter()
+            .map(IString::from)
+            .collect::<IString>();
+        assert_eq!(s, "abc");
+
+        let s = [IString::Static("a")].into_iter().collect::<IString>();
+        assert!(matches!(s, IString::Static("a")));
+
+        let rc = Rc::<str>::from("a");
+        let s = [IString::Rc(Rc::clone(&rc))]
+            .into_iter()
+            .collect::<IString>();
+        assert!(matches!(s, IString::Rc(ref s) if Rc::ptr_eq(s, &rc)));
+
+        let s = std::iter::empty::<IString>().collect::<IString>();
+        assert!(matches!(s, IString::Static("")));
+    }
+
+    #[test]
+    fn find_and_rfind() {
+        for s in [IString::Static("abcabc"), IString::Rc(Rc::from("abcabc"))] {
+            assert_eq!(s.find('b'), Some(1));
+            assert_eq!(s.rfind('b'), Some(4));
+            assert_eq!(s.find_str("bc"), Some(1));
+            assert_eq!(s.rfind_str("bc"), Some(4));
+            assert_eq!(s.find('x'), None);
+            assert_eq!(s.rfind('x'), None);
+            assert_eq!(s.find_str("cb"), None);
+            assert_eq!(s.rfind_str("cb"), None);
+            // `str::find` is still reachable through `Deref` with any pattern.
+            assert_eq!(s.find("bc"), Some(1));
+            assert_eq!(s.rfind(&['a', 'x'][..]), Some(3));
+        }
+    }
+
+    #[test]
+    fn eq_pointer_fast_path() {
+        let a = IString::from(String::from("foo"));
+        assert_eq!(a, a.clone());
+
+        // equal contents in distinct allocations
+        let b = IString::from(String::from("foo"));
+        assert!(!matches!((&a, &b), (IString::Rc(x), IString::Rc(y)) if Rc::ptr_eq(x, y)));
+        assert_eq!(a, b);
+        assert_ne!(a, IString::from(String::from("bar")));
+
+        let s = IString::Static("foo");
+        assert_eq!(s, s.clone());
+        assert_eq!(s, a);
+        assert_eq!(IString::Static(&"foobar"[..3]), s);
+        assert_ne!(IString::Static(&"foobar"[3..]), s);
+    }
+
+    #[test]
+    fn split_whitespace_istrings() {
+        let s = IString::from(String::from("  foo   bar\t baz  "));
+        let tokens = s.split_whitespace_istrings();
+        assert_eq!(tokens, ["foo", "bar", "baz"]);
+        assert!(tokens.iter().all(|token| matches!(token, IString::Rc(_))));
+
+        let s = IString::Static("\nfoo  bar\n");
+        let tokens = s.split_whitespace_istrings();
+        assert_eq!(tokens, ["foo", "bar"]);
+        assert!(tokens
+            .iter()
+            .all(|token| matches!(token, IString::Static(_))));
+
+        assert_eq!(IString::from("foo").split_whitespace_istrings(), ["foo"]);
+        assert!(matches!(
+            IString::from("").split_whitespace_istrings(),
+            IArray::Static(&[])
+        ));
+        assert!(matches!(
+            IString::from(String::from(" \t\n ")).split_whitespace_istrings(),
+            IArray::Static(&[])
+        ));
+
+        // `str::split_whitespace` is still reachable through `Deref`.
+        assert_eq!(s.split_whitespace().count(), 2);
+    }
+
+    #[test]
+    fn trim_matches_istring() {
+        let s = IString::Static("\"foo\"");
+        assert_eq!(s.trim_matches_istring('"'), "foo");
+        assert!(matches!(s.trim_matches_istring('"'), IString::Static(_)));
+        assert_eq!(s.trim_start_matches_istring('"'), "foo\"");
+        assert_eq!(s.trim_end_matches_istring('"'), "\"foo");
+
+        // nothing to trim
+        let trimmed = s.trim_matches_istring('\'');
+        assert!(matches!(trimmed, IString::Static(_)));
+        assert_eq!(trimmed, s);
+
+        let s = IString::from(String::from("''bar''"));
+        assert_eq!(s.trim_matches_istring('\''), "bar");
+        assert_eq!(s.trim_start_matches_istring('\''), "bar''");
+        assert_eq!(s.trim_end_matches_istring('\''), "''bar");
+        match (&s, s.trim_matches_istring('x')) {
+            (IString::Rc(a), IString::Rc(b)) => assert!(Rc::ptr_eq(a, &b)),
+            _ => panic!("expected Rc strings"),
+        }
+        assert_eq!(IString::from("''").trim_matches_istring('\''), "");
+
+        // `str::trim_matches` is still reachable through `Deref` with any pattern.
+        assert_eq!(s.trim_matches(&['\'', 'b'][..]), "ar");
+    }
+
+    #[test]
+    fn capitalize() {
+        let s = IString::from("foo bar").capitalize();
+        assert_eq!(s, "Foo bar");
+        assert!(matches!(s, IString::Rc(_)));
+
+        let s = IString::Static("Foo");
+        assert!(matches!(s.capitalize(), IString::Static("Foo")));
+        assert!(matches!(
+            IString::Static("42").capitalize(),
+            IString::Static("42")
+        ));
+
+        let s = IString::from(String::from("Bar"));
+        match (&s, s.capitalize()) {
+            (IString::Rc(a), IString::Rc(b)) => assert!(Rc::ptr_eq(a, &b)),
+            _ => panic!("expected Rc strings"),
+        }
+
+        assert_eq!(IString::from("").capitalize(), "");
+        assert_eq!(IString::from("éclair").capitalize(), "Éclair");
+        assert_eq!(IString::from("ßa").capitalize(), "SSa");
+    }
+
+    #[test]
+    fn variant_accessors() {
+        let s = IString::Static("foo");
+        assert_eq!(s.as_static(), Some("foo"));
+        assert!(s.as_rc().is_none());
+
+        let rc = Rc::<str>::from("bar");
+        let s = IString::Rc(rc.clone());
+        assert!(s.as_static().is_none());
+        assert!(Rc::ptr_eq(s.as_rc().unwrap(), &rc));
+    }
+
+    #[test]
+    fn match_offsets() {
+        let s = IString::from("foo bar foo baz foo");
+        assert_eq!(s.match_count("foo"), 3);
+        assert_eq!(s.match_offsets("foo"), [0, 8, 16]);
+        assert_eq!(s.match_offsets("bar"), [4]);
+
+        // overlapping occurrences are skipped
+        let s = IString::from("aaaa");
+        assert_eq!(s.match_count("aa"), 2);
+        assert_eq!(s.match_offsets("aa"), [0, 2]);
+
+        let s = IString::from("héllo");
+        assert_eq!(s.match_offsets("l"), [3, 4]);
+
+        assert_eq!(s.match_count("xyz"), 0);
+        assert_eq!(s.match_offsets("xyz"), IArray::EMPTY);
+
+        // `str::match_indices` is still reachable through `Deref` with any pattern.
+        assert_eq!(s.match_indices('l').count(), 2);
+    }
+
+    #[test]
+    fn from_str_owned() {
+        let owned = String::from("foo");
+        let s = IString::from_str_owned(&owned);
+        drop(owned);
+        assert_eq!(s, "foo");
+        assert!(matches!(s, IString::Rc(_)));
+
+        assert!(matches!(IString::from_str_owned("foo"), IString::Rc(_)));
+        assert!(matches!(IString::from("foo"), IString::Static("foo")));
+        assert!(matches!(
+            IString::from(Cow::Borrowed("foo")),
+            IString::Static("foo")
+        ));
+    }
+
+    #[test]
+    fn leak() {
+        let literal: &'static str = "foo";
+        let leaked = IString::Static(literal).leak();
+        assert!(std::ptr::eq(leaked, literal));
+
+        let s = IString::from(String::from("bar"));
+        let other = s.clone();
+        let leaked = s.leak();
+        assert_eq!(leaked, "bar");
+        assert!(matches!(&other, IString::Rc(rc) if std::ptr::eq(&**rc, leaked)));
+        drop(other);
+        assert_eq!(leaked, "bar");
+    }
+
+    #[test]
+    fn truncate_chars() {
+        let s = IString::Static("añ€😀b");
+        assert_eq!(s.truncate_chars(0), "");
+        assert_eq!(s.truncate_chars(2), "añ");
+        assert_eq!(s.truncate_chars(4), "añ€😀");
+        assert!(matches!(s.truncate_chars(3), IString::Static("añ€")));
+        match (&s, s.truncate_chars(5)) {
+            (IString::Static(a), IString::Static(b)) => assert!(std::ptr::eq(*a, b)),
+            _ => panic!("expected a static string"),
+        }
+
+        let s = IString::from(String::from("añ€😀b"));
+        assert_eq!(s.truncate_chars(3), "añ€");
+        match (&s, s.truncate_chars(10)) {
+            (IString::Rc(a), IString::Rc(b)) => assert!(Rc::ptr_eq(a, &b)),
+            _ => panic!("expected a reference counted string"),
+        }
+    }
+
+    #[test]
+    fn bytes() {
+        let s = IString::from("añ€");
+        assert!(s.bytes().eq("añ€".bytes()));
+        assert_eq!(IString::from(String::from("añ€")).bytes().len(), 6);
+    }
+
+    #[test]
+    fn as_byte_array() {
+        let s = IString::Static("añ€");
+        let bytes = s.as_byte_array();
+        assert_eq!(bytes, "añ€".as_bytes());
+        match (&s, &bytes) {
+            (IString::Static(a), IArray::Static(b)) => assert_eq!(a.as_ptr(), b.as_ptr()),
+            _ => panic!("expected a static byte array"),
+        }
+
+        let s = IString::from(String::from("añ€"));
+        let bytes = s.as_byte_array();
+        assert_eq!(bytes, "añ€".as_bytes());
+        assert!(matches!(bytes, IArray::Rc(_)));
+
+        assert!(matches!(
+            IString::default().as_byte_array(),
+            IArray::Static(&[])
+        ));
+    }
+
+    #[test]
+    fn replace_range() {
+        let s = IString::Static("añ€😀b");
+        let replaced = s.replace_range(3..10, "-");
+        assert_eq!(replaced, "añ-b");
+        assert!(matches!(replaced, IString::Rc(_)));
+        assert_eq!(s.replace_range(..1, ""), "ñ€😀b");
+
+        let s = IString::from(String::from("añ€😀b"));
+        assert_eq!(s.replace_range(3..3, "xyz"), "añxyz€😀b");
+        assert_eq!(s.replace_range(s.len_bytes().., "!"), "añ€😀b!");
+    }
+
+    #[test]
+    #[should_panic]
+    fn replace_range_not_char_boundary() {
+        IString::from("añ€").replace_range(2.., "");
+    }
+
+    #[test]
+    fn eq_opt() {
+        let foo = Some(IString::from("foo"));
+        assert!(IString::eq_opt(&foo, Some("foo")));
+        assert!(!IString::eq_opt(&foo, Some("bar")));
+        assert!(!IString::eq_opt(&foo, None));
+        assert!(!IString::eq_opt(&None, Some("foo")));
+        assert!(IString::eq_opt(&None, None));
+    }
+
+    #[test]
+    fn is_ascii() {
+        assert!(IString::default().is_ascii());
+        assert!(IString::Static("foo_bar").is_ascii());
+        assert!(IString::from(String::from("foo_bar")).is_ascii());
+        assert!(!IString::Static("añ").is_ascii());
+        assert!(!IString::from(String::from("añ")).is_ascii());
+    }
+
+    #[test]
+    fn make_ascii_uppercase() {
+        let mut unique = IString::from(String::from("añb"));
+        let ptr = unique.as_ptr();
+        unique.make_ascii_uppercase();
+        assert_eq!(unique, "AñB");
+        assert_eq!(unique.as_ptr(), ptr);
+
+        let mut shared = IString::from(String::from("añb"));
+        let other = shared.clone();
+        shared.make_ascii_uppercase();
+        assert_eq!(shared, "AñB");
+        assert_eq!(other, "añb");
+        assert_ne!(shared.as_ptr(), other.as_ptr());
+
+        let mut s = IString::Static("añb");
+        s.make_ascii_uppercase();
+        assert_eq!(s, "AñB");
+        assert!(matches!(s, IString::Rc(_)));
+
+        let mut s = IString::Static("AñB");
+        s.make_ascii_uppercase();
+        assert!(matches!(s, IString::Static("AñB")));
+    }
+
+    #[test]
+    fn try_into_string() {
+        let s = IString::from(String::from("foo"));
+        assert_eq!(s.try_into_string(), Ok(String::from("foo")));
+
+        let s = IString::from(String::from("foo"));
+        let other = s.clone();
+        match s.try_into_string() {
+            Err(IString::Rc(s)) => assert!(Rc::ptr_eq(&s, other.as_rc().unwrap())),
+            _ => panic!("expected the shared string back"),
+        }
+        assert_eq!(other.try_into_string(), Ok(String::from("foo")));
+
+        assert!(matches!(
+            IString::Static("foo").try_into_string(),
+            Err(IString::Static("foo"))
+        ));
+    }
+
+    #[test]
+    fn split_at_istring() {
+        let s = IString::Static("añ€b");
+        assert!(matches!(
+            s.split_at_istring(3),
+            (IString::Static("añ"), IString::Static("€b"))
+        ));
+        assert!(matches!(
+            s.split_at_istring(0),
+            (IString::Static(""), IString::Static("añ€b"))
+        ));
+
+        let s = IString::from(String::from("añ€b"));
+        let (a, b) = s.split_at_istring(6);
+        assert_eq!(a, "añ€");
+        assert_eq!(b, "b");
+        assert!(matches!((a, b), (IString::Rc(_), IString::Rc(_))));
+
+        // `str::split_at` is still reachable through `Deref`.
+        let halves: (&str, &str) = s.split_at(6);
+        assert_eq!(halves, ("añ€", "b"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_at_istring_not_char_boundary() {
+        IString::from("añ€b").split_at_istring(2);
+    }
+
+    #[test]
+    fn normalized() {
+        let s = IString::from(String::new());
+        assert!(matches!(s, IString::Rc(_)));
+        assert!(matches!(s.normalized(), IString::Static("")));
+        assert!(matches!(
+            IString::Static("").normalized(),
+            IString::Static("")
+        ));
+
+        assert!(matches!(
+            IString::Static("foo").normalized(),
+            IString::Static("foo")
+        ));
+        let s = IString::from(String::from("foo"));
+        match (&s, s.normalized()) {
+            (IString::Rc(a), IString::Rc(b)) => assert!(Rc::ptr_eq(a, &b)),
+            _ => panic!("expected the Rc to be reused"),
+        }
+    }
+
+    #[test]
+    fn strong_count() {
+        let s = IString::from(String::from("foo"));
+        assert_eq!(s.strong_count(), 1);
+        let other = s.clone();
+        assert_eq!(s.strong_count(), 2);
+        drop(other);
+        assert_eq!(s.strong_count(), 1);
+
+        let s = IString::Static("foo");
+        let _other = s.clone();
+        assert_eq!(s.strong_count(), 1);
+    }
+
+    #[test]
+    fn ptr_eq() {
+        let s = IString::from(String::from("foo"));
+        assert!(s.ptr_eq(&s.clone()));
+        let separate = IString::from(String::from("foo"));
+        assert_eq!(s, separate);
+        assert!(!s.ptr_eq(&separate));
+
+        static FOO: &str = "foo";
+        let s = IString::Static(FOO);
+        assert!(s.ptr_eq(&IString::Static(FOO)));
+        assert!(s.ptr_eq(&s.clone()));
+        assert!(!s.ptr_eq(&IString::Static(&FOO[..2])));
+        assert!(!s.ptr_eq(&separate));
+    }
 }
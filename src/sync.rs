@@ -16,3 +16,94 @@ pub use map::*;
 pub use string::*;
 
 impl<T: ?Sized> ImplicitClone for Rc<T> {}
+
+impl<T: ImplicitClone + 'static> IArray<T> {
+    /// Converts this array into its single-threaded counterpart.
+    ///
+    /// `Static` and `Single` arrays are converted without allocation. For `Rc` arrays, the
+    /// elements are cloned into a new [`Rc`](std::rc::Rc).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::{sync, unsync};
+    /// let array = sync::IArray::<u32>::from(vec![1, 2, 3]);
+    /// let array: unsync::IArray<u32> = array.to_unsync();
+    /// assert_eq!(array, [1, 2, 3]);
+    /// ```
+    pub fn to_unsync(&self) -> crate::unsync::IArray<T> {
+        match self {
+            Self::Static(a) => crate::unsync::IArray::Static(a),
+            Self::Rc(a) => crate::unsync::IArray::Rc(a.iter().cloned().collect()),
+            Self::Single(a) => crate::unsync::IArray::Single(a.clone()),
+        }
+    }
+}
+
+impl IString {
+    /// Converts this string into its single-threaded counterpart.
+    ///
+    /// `Static` strings are converted without allocation. For `Rc` strings, the content is copied
+    /// into a new [`Rc`](std::rc::Rc).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::{sync, unsync};
+    /// let s = sync::IString::from(String::from("foo"));
+    /// let s: unsync::IString = s.to_unsync();
+    /// assert_eq!(s, "foo");
+    /// ```
+    pub fn to_unsync(&self) -> crate::unsync::IString {
+        match self {
+            Self::Static(s) => crate::unsync::IString::Static(s),
+            Self::Rc(s) => crate::unsync::IString::Rc(std::rc::Rc::from(&**s)),
+        }
+    }
+}
+
+#[cfg(feature = "map")]
+impl<K, V> IMap<K, V>
+where
+    K: Eq + std::hash::Hash + ImplicitClone + 'static,
+    V: PartialEq + ImplicitClone + 'static,
+{
+    /// Converts this map into its single-threaded counterpart.
+    ///
+    /// `Static` maps are converted without allocation. For `Rc` maps, the entries are cloned into
+    /// a new [`Rc`](std::rc::Rc).
+    #[cfg_attr(docsrs, doc(cfg(feature = "map")))]
+    pub fn to_unsync(&self) -> crate::unsync::IMap<K, V> {
+        match self {
+            Self::Static(a) => crate::unsync::IMap::Static(a),
+            Self::Rc(a) => crate::unsync::IMap::Rc(std::rc::Rc::new((**a).clone())),
+        }
+    }
+}
+
+/// Converts the array with [`IArray::to_unsync`].
+impl<T: ImplicitClone + 'static> From<IArray<T>> for crate::unsync::IArray<T> {
+    fn from(a: IArray<T>) -> Self {
+        a.to_unsync()
+    }
+}
+
+/// Converts the string with [`IString::to_unsync`].
+impl From<IString> for crate::unsync::IString {
+    fn from(s: IString) -> Self {
+        s.to_unsync()
+    }
+}
+
+/// Converts the map with [`IMap::to_unsync`].
+#[cfg(feature = "map")]
+#[cfg_attr(docsrs, doc(cfg(feature = "map")))]
+impl<K, V> From<IMap<K, V>> for crate::unsync::IMap<K, V>
+where
+    K: Eq + std::hash::Hash + ImplicitClone + 'static,
+    V: PartialEq + ImplicitClone + 'static,
+{
+    fn from(m: IMap<K, V>) -> Self {
+        m.to_unsync()
+    }
+}
@@ -0,0 +1,45 @@
+//! Fixtures shared by the unit tests of the `sync` and `unsync` types.
+
+use std::cell::Cell;
+
+use crate::ImplicitClone;
+
+thread_local! {
+    static CLONES: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Returns the number of times a `Counted` has been cloned on this thread.
+pub(crate) fn clones() -> usize {
+    CLONES.with(Cell::get)
+}
+
+/// A value that records every clone in a thread-local counter.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub(crate) struct Counted(pub(crate) u32);
+
+impl Clone for Counted {
+    fn clone(&self) -> Self {
+        CLONES.with(|c| c.set(c.get() + 1));
+        Counted(self.0)
+    }
+}
+
+impl ImplicitClone for Counted {}
+
+/// A value that panics when compared, to check that equality short-circuits on pointers.
+#[derive(Debug)]
+pub(crate) struct PanicOnEq;
+
+impl PartialEq for PanicOnEq {
+    fn eq(&self, _: &Self) -> bool {
+        panic!("values should not be compared");
+    }
+}
+
+impl Clone for PanicOnEq {
+    fn clone(&self) -> Self {
+        PanicOnEq
+    }
+}
+
+impl ImplicitClone for PanicOnEq {}
@@ -16,3 +16,201 @@ pub use map::*;
 pub use string::*;
 
 impl<T: ?Sized> ImplicitClone for Rc<T> {}
+
+impl<T: ImplicitClone + 'static> IArray<T> {
+    /// Converts this array into its thread-safe counterpart.
+    ///
+    /// `Static` and `Single` arrays are converted without allocation. For `Rc` arrays, the
+    /// elements are cloned into a new [`Arc`](std::sync::Arc).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::{sync, unsync};
+    /// let array = unsync::IArray::<u32>::from(vec![1, 2, 3]);
+    /// let array: sync::IArray<u32> = array.to_sync();
+    /// assert_eq!(array, [1, 2, 3]);
+    /// ```
+    pub fn to_sync(&self) -> crate::sync::IArray<T> {
+        match self {
+            Self::Static(a) => crate::sync::IArray::Static(a),
+            Self::Rc(a) => crate::sync::IArray::Rc(a.iter().cloned().collect()),
+            Self::Single(a) => crate::sync::IArray::Single(a.clone()),
+        }
+    }
+}
+
+impl IString {
+    /// Converts this string into its thread-safe counterpart.
+    ///
+    /// `Static` strings are converted without allocation. For `Rc` strings, the content is copied
+    /// into a new [`Arc`](std::sync::Arc).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use implicit_clone::{sync, unsync};
+    /// let s = unsync::IString::from(String::from("foo"));
+    /// let s: sync::IString = s.to_sync();
+    /// assert_eq!(s, "foo");
+    /// ```
+    pub fn to_sync(&self) -> crate::sync::IString {
+        match self {
+            Self::Static(s) => crate::sync::IString::Static(s),
+            Self::Rc(s) => crate::sync::IString::Rc(std::sync::Arc::from(&**s)),
+        }
+    }
+}
+
+#[cfg(feature = "map")]
+impl<K, V> IMap<K, V>
+where
+    K: Eq + std::hash::Hash + ImplicitClone + 'static,
+    V: PartialEq + ImplicitClone + 'static,
+{
+    /// Converts this map into its thread-safe counterpart.
+    ///
+    /// `Static` maps are converted without allocation. For `Rc` maps, the entries are cloned into
+    /// a new [`Arc`](std::sync::Arc).
+    #[cfg_attr(docsrs, doc(cfg(feature = "map")))]
+    pub fn to_sync(&self) -> crate::sync::IMap<K, V> {
+        match self {
+            Self::Static(a) => crate::sync::IMap::Static(a),
+            Self::Rc(a) => crate::sync::IMap::Rc(std::sync::Arc::new((**a).clone())),
+        }
+    }
+}
+
+/// Converts the array with [`IArray::to_sync`].
+impl<T: ImplicitClone + 'static> From<IArray<T>> for crate::sync::IArray<T> {
+    fn from(a: IArray<T>) -> Self {
+        a.to_sync()
+    }
+}
+
+/// Converts the string with [`IString::to_sync`].
+impl From<IString> for crate::sync::IString {
+    fn from(s: IString) -> Self {
+        s.to_sync()
+    }
+}
+
+/// Converts the map with [`IMap::to_sync`].
+#[cfg(feature = "map")]
+#[cfg_attr(docsrs, doc(cfg(feature = "map")))]
+impl<K, V> From<IMap<K, V>> for crate::sync::IMap<K, V>
+where
+    K: Eq + std::hash::Hash + ImplicitClone + 'static,
+    V: PartialEq + ImplicitClone + 'static,
+{
+    fn from(m: IMap<K, V>) -> Self {
+        m.to_sync()
+    }
+}
+
+#[cfg(test)]
+mod test_conversion {
+    use super::*;
+    use crate::sync;
+
+    #[test]
+    fn array_round_trip() {
+        let array = IArray::<u32>::Static(&[1, 2, 3]);
+        let sync_array = array.to_sync();
+        assert!(matches!(sync_array, sync::IArray::Static(&[1, 2, 3])));
+        assert!(matches!(sync_array.to_unsync(), IArray::Static(&[1, 2, 3])));
+
+        let array = IArray::<u32>::from(vec![1, 2, 3]);
+        let sync_array = array.to_sync();
+        assert!(matches!(sync_array, sync::IArray::Rc(_)));
+        assert_eq!(sync_array, [1, 2, 3]);
+        let array = sync_array.to_unsync();
+        assert!(matches!(array, IArray::Rc(_)));
+        assert_eq!(array, [1, 2, 3]);
+
+        let array = IArray::<u32>::Single([1]);
+        assert!(matches!(array.to_sync(), sync::IArray::Single([1])));
+        assert!(matches!(array.to_sync().to_unsync(), IArray::Single([1])));
+    }
+
+    #[test]
+    fn string_round_trip() {
+        let s = IString::Static("foo");
+        assert!(matches!(s.to_sync(), sync::IString::Static("foo")));
+        assert!(matches!(s.to_sync().to_unsync(), IString::Static("foo")));
+
+        let s = IString::from(String::from("foo"));
+        let sync_s = s.to_sync();
+        assert!(matches!(sync_s, sync::IString::Rc(_)));
+        assert_eq!(sync_s, "foo");
+        let s = sync_s.to_unsync();
+        assert!(matches!(s, IString::Rc(_)));
+        assert_eq!(s, "foo");
+    }
+
+    #[cfg(feature = "map")]
+    #[test]
+    fn map_round_trip() {
+        let map = IMap::<&str, u32>::Static(&[("foo", 1)]);
+        assert!(matches!(map.to_sync(), sync::IMap::Static(_)));
+        assert!(matches!(map.to_sync().to_unsync(), IMap::Static(_)));
+
+        let map = [("foo", 1), ("bar", 2)]
+            .into_iter()
+            .collect::<IMap<&str, u32>>();
+        let sync_map = map.to_sync();
+        assert!(matches!(sync_map, sync::IMap::Rc(_)));
+        assert_eq!(sync_map.get("bar"), Some(2));
+        let map = sync_map.to_unsync();
+        assert!(matches!(map, IMap::Rc(_)));
+        assert_eq!(map.get("foo"), Some(1));
+    }
+
+    #[test]
+    fn array_from() {
+        let array = IArray::<u32>::Static(&[1, 2, 3]);
+        let sync_array = sync::IArray::from(array);
+        assert!(matches!(sync_array, sync::IArray::Static(&[1, 2, 3])));
+        assert!(matches!(
+            IArray::from(sync_array),
+            IArray::Static(&[1, 2, 3])
+        ));
+
+        let sync_array = sync::IArray::from(IArray::<u32>::from(vec![1, 2, 3]));
+        assert!(matches!(sync_array, sync::IArray::Rc(_)));
+        assert_eq!(sync_array, [1, 2, 3]);
+        let array = IArray::from(sync_array);
+        assert!(matches!(array, IArray::Rc(_)));
+        assert_eq!(array, [1, 2, 3]);
+    }
+
+    #[test]
+    fn string_from() {
+        let s = sync::IString::from(IString::Static("foo"));
+        assert!(matches!(s, sync::IString::Static("foo")));
+        assert!(matches!(IString::from(s), IString::Static("foo")));
+
+        let s = sync::IString::from(IString::from(String::from("foo")));
+        assert!(matches!(s, sync::IString::Rc(_)));
+        let s = IString::from(s);
+        assert!(matches!(s, IString::Rc(_)));
+        assert_eq!(s, "foo");
+    }
+
+    #[cfg(feature = "map")]
+    #[test]
+    fn map_from() {
+        let map = sync::IMap::from(IMap::<&str, u32>::Static(&[("foo", 1)]));
+        assert!(matches!(map, sync::IMap::Static(_)));
+        assert!(matches!(IMap::from(map), IMap::Static(_)));
+
+        let map = [("foo", 1), ("bar", 2)]
+            .into_iter()
+            .collect::<IMap<&str, u32>>();
+        let sync_map = sync::IMap::from(map);
+        assert!(matches!(sync_map, sync::IMap::Rc(_)));
+        let map = IMap::from(sync_map);
+        assert!(matches!(map, IMap::Rc(_)));
+        assert_eq!(map.get("bar"), Some(2));
+    }
+}